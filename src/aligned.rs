@@ -0,0 +1,265 @@
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::ops::Deref;
+
+use bytemuck::Pod;
+
+use super::Error;
+
+pub trait Element: Pod + sealed::Sealed {
+    #[doc(hidden)]
+    const NAME: &'static str;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+const NAMES: [&str; 7] = [
+    "$serde_mincode::Aligned1",
+    "$serde_mincode::Aligned2",
+    "$serde_mincode::Aligned4",
+    "$serde_mincode::Aligned8",
+    "$serde_mincode::Aligned16",
+    "$serde_mincode::AlignedF32",
+    "$serde_mincode::AlignedF64",
+];
+
+pub(crate) const MAX_ALIGN: usize = 16;
+
+pub(crate) fn element_size(name: &str) -> Option<usize> {
+    match NAMES.iter().position(|&name1| name1 == name)? {
+        5 => Some(size_of::<f32>()),
+        6 => Some(size_of::<f64>()),
+        index => Some(1 << index),
+    }
+}
+
+/// Whether all NaNs are normalised if the elements are floating point numbers.
+pub(crate) fn is_canonical(name: &str, bytes: &[u8]) -> bool {
+    if name == NAMES[5] {
+        return bytes.chunks_exact(size_of::<f32>()).all(|bytes| {
+            let value = f32::from_ne_bytes(bytes.try_into().unwrap());
+            !value.is_nan() || value.to_bits() == f32::NAN.to_bits()
+        });
+    }
+
+    if name == NAMES[6] {
+        return bytes.chunks_exact(size_of::<f64>()).all(|bytes| {
+            let value = f64::from_ne_bytes(bytes.try_into().unwrap());
+            !value.is_nan() || value.to_bits() == f64::NAN.to_bits()
+        });
+    }
+
+    true
+}
+
+thread_local! {
+    static BULK: Cell<bool> = const { Cell::new(false) };
+}
+
+pub(crate) fn with_bulk<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    struct Restore(bool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            BULK.set(self.0);
+        }
+    }
+
+    let _restore = Restore(BULK.replace(true));
+
+    f()
+}
+
+pub(crate) struct Bulk<'a, T>(pub(crate) &'a [T]);
+
+impl<T> serde::ser::Serialize for Bulk<'_, T>
+where
+    T: Element + serde::ser::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        if BULK.replace(false) {
+            serializer.serialize_bytes(bytemuck::cast_slice(self.0))
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+macro_rules! impl_element {
+    ($($ty:ty => $index:literal),*) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl Element for $ty {
+                const NAME: &'static str = NAMES[$index];
+            }
+        )*
+    };
+}
+
+impl_element!(u8 => 0, u16 => 1, u32 => 2, u64 => 3, u128 => 4);
+impl_element!(i8 => 0, i16 => 1, i32 => 2, i64 => 3, i128 => 4);
+impl_element!(f32 => 5, f64 => 6);
+
+/// A slice of numbers which is borrowed from the input if it was encoded using [`Config::aligned`](crate::Config::aligned)
+///
+/// Otherwise or if the input itself is not suitably aligned, the elements are copied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlignedSlice<'a, T>(pub Cow<'a, [T]>)
+where
+    T: Element;
+
+impl<T> AlignedSlice<'_, T>
+where
+    T: Element,
+{
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.0, Cow::Borrowed(_))
+    }
+
+    pub fn into_owned(self) -> Vec<T> {
+        self.0.into_owned()
+    }
+}
+
+impl<T> Deref for AlignedSlice<'_, T>
+where
+    T: Element,
+{
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a, T> From<&'a [T]> for AlignedSlice<'a, T>
+where
+    T: Element,
+{
+    fn from(slice: &'a [T]) -> Self {
+        Self(Cow::Borrowed(slice))
+    }
+}
+
+impl<T> From<Vec<T>> for AlignedSlice<'_, T>
+where
+    T: Element,
+{
+    fn from(vec: Vec<T>) -> Self {
+        Self(Cow::Owned(vec))
+    }
+}
+
+impl<T> serde::ser::Serialize for AlignedSlice<'_, T>
+where
+    T: Element + serde::ser::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.serialize_newtype_struct(T::NAME, &Bulk(&self.0))
+    }
+}
+
+impl<'de: 'a, 'a, T> serde::de::Deserialize<'de> for AlignedSlice<'a, T>
+where
+    T: Element + serde::de::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(T::NAME, AlignedVisitor(PhantomData))
+    }
+}
+
+struct AlignedVisitor<'a, T>(PhantomData<&'a T>);
+
+impl<'de: 'a, 'a, T> serde::de::Visitor<'de> for AlignedVisitor<'a, T>
+where
+    T: Element + serde::de::Deserialize<'de>,
+{
+    type Value = AlignedSlice<'a, T>;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("aligned slice")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let mut vec = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+
+        while let Some(elem) = seq.next_element()? {
+            vec.push(elem);
+        }
+
+        Ok(vec.into())
+    }
+
+    fn visit_borrowed_bytes<E>(self, bytes: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        match bytemuck::try_cast_slice(bytes) {
+            Ok(slice) => Ok(AlignedSlice(Cow::Borrowed(slice))),
+            Err(_) => self.visit_bytes(bytes),
+        }
+    }
+
+    fn visit_bytes<E>(self, bytes: &[u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(bytemuck::pod_collect_to_vec(bytes).into())
+    }
+}
+
+pub(crate) struct AlignedDecoder<'de> {
+    pub(crate) bytes: Cow<'de, [u8]>,
+}
+
+impl<'de> serde::de::Deserializer<'de> for AlignedDecoder<'de> {
+    type Error = Box<Error>;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        match self.bytes {
+            Cow::Borrowed(bytes) => visitor.visit_borrowed_bytes(bytes),
+            Cow::Owned(bytes) => visitor.visit_byte_buf(bytes),
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map struct enum
+        identifier ignored_any
+    }
+}
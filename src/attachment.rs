@@ -0,0 +1,108 @@
+//! Out-of-band byte fields via `#[serde(with = "serde_mincode::attachment")]`
+//!
+//! When encoding using [`serialize_with_attachments`](crate::serialize_with_attachments),
+//! the body contains only an index into the returned attachments which must be passed
+//! to [`deserialize_with_attachments`](crate::deserialize_with_attachments).
+//! Otherwise, such fields are encoded inline. Only length-prefixed types like `Vec<u8>`,
+//! `serde_bytes::ByteBuf` or `String` are supported.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::from_utf8;
+
+use super::Error;
+
+pub(crate) const NAME: &str = "$serde_mincode::Attachment";
+
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: serde::ser::Serialize + ?Sized,
+    S: serde::ser::Serializer,
+{
+    serializer.serialize_newtype_struct(NAME, value)
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: serde::de::Deserialize<'de>,
+    D: serde::de::Deserializer<'de>,
+{
+    deserializer.deserialize_newtype_struct(NAME, AttachmentVisitor(PhantomData))
+}
+
+struct AttachmentVisitor<T>(PhantomData<T>);
+
+impl<'de, T> serde::de::Visitor<'de> for AttachmentVisitor<T>
+where
+    T: serde::de::Deserialize<'de>,
+{
+    type Value = T;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("attachment")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        T::deserialize(deserializer)
+    }
+}
+
+pub(crate) struct AttachmentDecoder<'de> {
+    pub(crate) bytes: &'de [u8],
+}
+
+impl<'de> serde::de::Deserializer<'de> for AttachmentDecoder<'de> {
+    type Error = Box<Error>;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_borrowed_bytes(self.bytes)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let Ok(value) = from_utf8(self.bytes) else {
+            return Error::InvalidStr.into();
+        };
+
+        visitor.visit_borrowed_str(value)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let mut seq =
+            serde::de::value::SeqDeserializer::<_, Self::Error>::new(self.bytes.iter().copied());
+
+        let value = visitor.visit_seq(&mut seq)?;
+
+        seq.end()?;
+
+        Ok(value)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char bytes byte_buf
+        option unit unit_struct newtype_struct tuple tuple_struct map struct enum
+        identifier ignored_any
+    }
+}
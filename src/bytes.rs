@@ -0,0 +1,138 @@
+//! Zero-copy `Bytes` fields via `#[serde(with = "serde_mincode::bytes")]`
+//!
+//! When decoding using [`deserialize_from_bytes`](crate::deserialize_from_bytes),
+//! such fields reference the input instead of copying it. When encoding using
+//! [`serialize_vectored`](crate::serialize_vectored), such fields are referenced by the output.
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+
+use bytes::Bytes;
+
+thread_local! {
+    static SOURCE: RefCell<Option<Bytes>> = const { RefCell::new(None) };
+    static VECTORED: Cell<bool> = const { Cell::new(false) };
+    static SHARED: RefCell<Option<Bytes>> = const { RefCell::new(None) };
+}
+
+pub(crate) fn with_source<F, R>(source: &Bytes, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    struct Restore(Option<Bytes>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SOURCE.set(self.0.take());
+        }
+    }
+
+    let _restore = Restore(SOURCE.replace(Some(source.clone())));
+
+    f()
+}
+
+pub(crate) fn with_vectored<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    struct Restore(bool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            VECTORED.set(self.0);
+        }
+    }
+
+    let _restore = Restore(VECTORED.replace(true));
+
+    f()
+}
+
+pub(crate) fn take_shared(value: &[u8]) -> Option<Bytes> {
+    SHARED.with_borrow_mut(|shared| {
+        let bytes = shared.as_ref()?;
+
+        (bytes.as_ptr() == value.as_ptr() && bytes.len() == value.len())
+            .then(|| shared.take())
+            .flatten()
+    })
+}
+
+pub fn serialize<S>(bytes: &Bytes, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::ser::Serializer,
+{
+    if !VECTORED.get() {
+        return serializer.serialize_bytes(bytes);
+    }
+
+    SHARED.set(Some(bytes.clone()));
+
+    let res = serializer.serialize_bytes(bytes);
+
+    SHARED.set(None);
+
+    res
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Bytes, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    deserializer.deserialize_bytes(BytesVisitor)
+}
+
+struct BytesVisitor;
+
+impl<'de> serde::de::Visitor<'de> for BytesVisitor {
+    type Value = Bytes;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("bytes")
+    }
+
+    fn visit_borrowed_bytes<E>(self, value: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        let bytes = SOURCE.with_borrow(|source| {
+            let source = source.as_ref()?;
+
+            let range = source.as_ptr_range();
+            let subset = value.as_ptr_range();
+
+            (range.start <= subset.start && subset.end <= range.end)
+                .then(|| source.slice_ref(value))
+        });
+
+        Ok(bytes.unwrap_or_else(|| Bytes::copy_from_slice(value)))
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(Bytes::copy_from_slice(value))
+    }
+
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(Bytes::from(value))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let mut value = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+
+        while let Some(byte) = seq.next_element()? {
+            value.push(byte);
+        }
+
+        Ok(Bytes::from(value))
+    }
+}
@@ -0,0 +1,83 @@
+use std::marker::PhantomData;
+use std::mem::size_of;
+
+use bytes::{Buf, BytesMut};
+
+use super::de::Decoder;
+use super::ser::Encoder;
+use super::{Config, Error};
+
+pub struct MincodeCodec<T> {
+    max_len: usize,
+    config: Config,
+    _marker: PhantomData<fn(T) -> T>,
+}
+
+impl<T> MincodeCodec<T> {
+    pub fn new(max_len: usize) -> Self {
+        Self::with_config(max_len, Config::default())
+    }
+
+    pub fn with_config(max_len: usize, config: Config) -> Self {
+        Self {
+            max_len,
+            config,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> tokio_util::codec::Encoder<T> for MincodeCodec<T>
+where
+    T: serde::ser::Serialize,
+{
+    type Error = Box<Error>;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let start = dst.len();
+
+        let res = Encoder::with_config(&mut *dst, self.config)
+            .serialize_frame(&item)
+            .and_then(|encoder| {
+                if encoder.bytes_written() - size_of::<u32>() > self.max_len {
+                    return Error::FrameTooLarge.into();
+                }
+
+                Ok(())
+            });
+
+        if res.is_err() {
+            dst.truncate(start);
+        }
+
+        res
+    }
+}
+
+impl<T> tokio_util::codec::Decoder for MincodeCodec<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    type Item = T;
+    type Error = Box<Error>;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let mut decoder = Decoder::new(src);
+
+        let frame = match decoder.decode_frame(self.max_len) {
+            Ok(frame) => frame,
+            Err(err) => {
+                src.reserve(err.needed().ok_or(err)?);
+
+                return Ok(None);
+            }
+        };
+
+        let value = self.config.deserialize_whole(frame)?;
+
+        let len = src.len() - decoder.remaining().len();
+        src.advance(len);
+
+        Ok(Some(value))
+    }
+}
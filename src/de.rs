@@ -1,27 +1,235 @@
-use std::mem::size_of;
+use std::borrow::Cow;
+use std::mem::{size_of, take};
+use std::ops::Range;
 use std::str::from_utf8;
 
-use super::Error;
+use serde::de::value::{
+    BorrowedBytesDeserializer, U32Deserializer, U64Deserializer, U8Deserializer,
+};
+
+#[cfg(feature = "bytemuck")]
+use super::aligned::{self, AlignedDecoder};
+use super::attachment::{self, AttachmentDecoder};
+use super::indexed::{self, Elements, Table};
+use super::lazy;
+use super::seq_iter;
+use super::{Config, Error};
 
 pub struct Decoder<'de> {
     buf: &'de [u8],
+    chunks: &'de [&'de [u8]],
+    chunks_len: usize,
+    len: usize,
+    bitmaps: usize,
+    attachments: Option<&'de [&'de [u8]]>,
+    projection: Option<&'de [&'de str]>,
+    prefix: String,
+    target: Option<&'de str>,
+    located: Option<Range<usize>>,
+    config: Config,
+}
+
+#[derive(Clone, Copy)]
+enum Selection {
+    Whole,
+    Nested,
 }
 
 impl<'de> Decoder<'de> {
     pub fn new(buf: &'de [u8]) -> Self {
-        Self { buf }
+        Self::with_config(buf, Config::default())
+    }
+
+    pub fn with_config(buf: &'de [u8], config: Config) -> Self {
+        Self {
+            buf,
+            chunks: &[],
+            chunks_len: 0,
+            len: buf.len(),
+            bitmaps: 0,
+            attachments: None,
+            projection: None,
+            prefix: String::new(),
+            target: None,
+            located: None,
+            config,
+        }
+    }
+
+    pub fn from_chunks(chunks: &'de [&'de [u8]]) -> Self {
+        Self::from_chunks_with_config(chunks, Config::default())
+    }
+
+    /// Decode from non-contiguous input, copying only values which straddle chunk boundaries.
+    pub fn from_chunks_with_config(chunks: &'de [&'de [u8]], config: Config) -> Self {
+        let chunks_len = chunks.iter().map(|chunk| chunk.len()).sum();
+
+        Self {
+            buf: &[],
+            chunks,
+            chunks_len,
+            len: chunks_len,
+            bitmaps: 0,
+            attachments: None,
+            projection: None,
+            prefix: String::new(),
+            target: None,
+            located: None,
+            config,
+        }
+    }
+
+    /// Continue positions from where the input was split off the encoded value.
+    pub(crate) fn with_offset(mut self, offset: usize) -> Self {
+        self.len += offset;
+        self
+    }
+
+    /// Restart positions from the remaining input, e.g. because it starts a separately encoded value.
+    pub(crate) fn restart(&mut self) {
+        self.len = self.buf.len() + self.chunks_len;
+    }
+
+    pub(crate) fn with_attachments(mut self, attachments: &'de [&'de [u8]]) -> Self {
+        self.attachments = Some(attachments);
+        self
+    }
+
+    pub(crate) fn with_projection(mut self, paths: &'de [&'de str]) -> Self {
+        self.projection = Some(paths);
+        self
+    }
+
+    pub(crate) fn with_target(mut self, path: &'de str) -> Self {
+        self.target = Some(path);
+        self
+    }
+
+    pub(crate) fn located(&self) -> Option<Range<usize>> {
+        self.located.clone()
+    }
+
+    pub fn position(&self) -> usize {
+        self.len - self.buf.len() - self.chunks_len
+    }
+
+    /// The position as seen when encoding, i.e. without the presence bitmaps of the enclosing structs.
+    fn offset(&self) -> usize {
+        self.position() - self.bitmaps
+    }
+
+    /// The remaining input, limited to the current chunk when decoding from chunks.
+    pub fn remaining(&self) -> &'de [u8] {
+        self.buf
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty() && self.chunks_len == 0
+    }
+
+    /// Reject trailing data in canonical mode as it would allow multiple encodings of the same value.
+    pub(crate) fn finish(&self) -> Result<(), Box<Error>> {
+        if self.config.canonical && !self.is_empty() {
+            return Error::TrailingData.into();
+        }
+
+        Ok(())
+    }
+
+    fn next_chunk(&mut self) {
+        let (&chunk, chunks) = self.chunks.split_first().unwrap();
+
+        self.buf = chunk;
+        self.chunks = chunks;
+        self.chunks_len -= chunk.len();
+    }
+
+    fn available(&self, len: usize) -> Result<(), Box<Error>> {
+        let available = self.buf.len() + self.chunks_len;
+
+        if len > available {
+            return Error::MissingData(len - available).into();
+        }
+
+        Ok(())
+    }
+
+    fn is_target(&self, field: &str) -> bool {
+        self.target
+            .and_then(|target| target.strip_prefix(&*self.prefix))
+            .and_then(|target| target.strip_prefix(field))
+            == Some("")
+    }
+
+    fn select(&self, field: &str) -> Option<Selection> {
+        if let Some(target) = self.target {
+            let rest = target
+                .strip_prefix(&*self.prefix)
+                .and_then(|target| target.strip_prefix(field));
+
+            return match rest {
+                Some(rest) if rest.starts_with('.') => Some(Selection::Nested),
+                _ => Some(Selection::Whole),
+            };
+        }
+
+        let Some(paths) = self.projection else {
+            return Some(Selection::Whole);
+        };
+
+        let mut selection = None;
+
+        for path in paths {
+            let Some(rest) = path
+                .strip_prefix(&*self.prefix)
+                .and_then(|path| path.strip_prefix(field))
+            else {
+                continue;
+            };
+
+            if rest.is_empty() {
+                return Some(Selection::Whole);
+            }
+
+            if rest.starts_with('.') {
+                selection = Some(Selection::Nested);
+            }
+        }
+
+        selection
+    }
+
+    fn decode_selected<F, R>(&mut self, field: &str, selection: Selection, f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        match selection {
+            Selection::Whole => {
+                let projection = self.projection.take();
+                let target = self.target.take();
+                let res = f(self);
+                self.projection = projection;
+                self.target = target;
+                res
+            }
+            Selection::Nested => {
+                let len = self.prefix.len();
+                self.prefix.push_str(field);
+                self.prefix.push('.');
+                let res = f(self);
+                self.prefix.truncate(len);
+                res
+            }
+        }
     }
 }
 
 macro_rules! impl_decode {
     ($method:ident: $ty:ty) => {
         fn $method(&mut self) -> Result<$ty, Box<Error>> {
-            let Some((bytes, rest)) = self.buf.split_at_checked(size_of::<$ty>()) else {
-                return Error::MissingData.into();
-            };
-            self.buf = rest;
+            let bytes = self.decode_array()?;
 
-            let value = <$ty>::from_ne_bytes(bytes.try_into().unwrap());
+            let value = <$ty>::from_ne_bytes(bytes);
 
             Ok(value)
         }
@@ -44,15 +252,155 @@ impl<'de> Decoder<'de> {
     impl_decode!(decode_f32: f32);
     impl_decode!(decode_f64: f64);
 
-    fn decode_bytes(&mut self) -> Result<&'de [u8], Box<Error>> {
-        let len = self.decode_u32()?;
+    fn decode_array<const N: usize>(&mut self) -> Result<[u8; N], Box<Error>> {
+        let Some((bytes, rest)) = self.buf.split_first_chunk() else {
+            let mut bytes = [0; N];
+            self.decode_straddling(&mut bytes)?;
+
+            return Ok(bytes);
+        };
+        self.buf = rest;
+
+        Ok(*bytes)
+    }
 
-        let Some((bytes, rest)) = self.buf.split_at_checked(len as usize) else {
-            return Error::MissingData.into();
+    fn decode_slice(&mut self, len: usize) -> Result<Cow<'de, [u8]>, Box<Error>> {
+        let Some((bytes, rest)) = self.buf.split_at_checked(len) else {
+            return self.decode_slice_straddling(len);
         };
         self.buf = rest;
 
-        Ok(bytes)
+        Ok(Cow::Borrowed(bytes))
+    }
+
+    #[cold]
+    fn decode_slice_straddling(&mut self, len: usize) -> Result<Cow<'de, [u8]>, Box<Error>> {
+        if self.buf.is_empty() && !self.chunks.is_empty() {
+            self.next_chunk();
+
+            return self.decode_slice(len);
+        }
+
+        self.available(len)?;
+
+        let mut bytes = vec![0; len];
+        self.decode_straddling(&mut bytes)?;
+
+        Ok(Cow::Owned(bytes))
+    }
+
+    #[cold]
+    fn decode_straddling(&mut self, mut bytes: &mut [u8]) -> Result<(), Box<Error>> {
+        self.available(bytes.len())?;
+
+        while !bytes.is_empty() {
+            if self.buf.is_empty() {
+                self.next_chunk();
+            }
+
+            let len = bytes.len().min(self.buf.len());
+
+            let (head, tail) = take(&mut bytes).split_at_mut(len);
+            head.copy_from_slice(&self.buf[..len]);
+
+            self.buf = &self.buf[len..];
+            bytes = tail;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "bytemuck")]
+    fn decode_pad(&mut self, size: usize, rest: usize) -> Result<(), Box<Error>> {
+        let offset = self.offset() + 1 + rest;
+
+        let pad = self.decode_u8()? as usize;
+        let bytes = self.decode_slice(pad)?;
+
+        if self.config.canonical
+            && (pad != (size - offset % size) % size || bytes.iter().any(|&byte| byte != 0))
+        {
+            return Error::NonCanonical.into();
+        }
+
+        Ok(())
+    }
+
+    fn decode_borrowed(&mut self, len: usize) -> Result<&'de [u8], Box<Error>> {
+        match self.decode_slice(len)? {
+            Cow::Borrowed(bytes) => Ok(bytes),
+            Cow::Owned(_) => Error::NotSupported.into(),
+        }
+    }
+
+    fn decode_bytes(&mut self) -> Result<Cow<'de, [u8]>, Box<Error>> {
+        let len = self.decode_u32()?;
+
+        self.decode_slice(len as usize)
+    }
+
+    pub(crate) fn decode_frame_len(&mut self, max_len: usize) -> Result<usize, Box<Error>> {
+        let len = self.decode_u32()? as usize;
+
+        if len > max_len {
+            return Error::FrameTooLarge.into();
+        }
+
+        Ok(len)
+    }
+
+    pub(crate) fn decode_frame(&mut self, max_len: usize) -> Result<&'de [u8], Box<Error>> {
+        let len = self.decode_frame_len(max_len)?;
+
+        self.decode_borrowed(len)
+    }
+
+    fn decode_struct<V>(
+        &mut self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Box<Error>>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        if !self.config.presence_bitmap && !self.config.field_lengths && self.target.is_none() {
+            return serde::de::Deserializer::deserialize_tuple(self, fields.len(), visitor);
+        }
+
+        let bitmap = if self.config.presence_bitmap {
+            let bitmap = self.decode_slice(fields.len().div_ceil(8))?;
+
+            let unused = match fields.len() % 8 {
+                0 => 0,
+                bits => bitmap[bitmap.len() - 1] >> bits,
+            };
+
+            if self.config.canonical && unused != 0 {
+                return Error::NonCanonical.into();
+            }
+
+            Some(bitmap)
+        } else {
+            None
+        };
+
+        let bitmaps = bitmap.as_ref().map_or(0, |bitmap| bitmap.len());
+        self.bitmaps += bitmaps;
+
+        let mut decoder = StructDecoder {
+            this: self,
+            fields,
+            bitmap,
+            index: 0,
+            pending: None,
+        };
+
+        let value = visitor.visit_map(&mut decoder)?;
+
+        let this = decoder.end()?;
+        this.bitmaps -= bitmaps;
+
+        Ok(value)
     }
 }
 
@@ -105,8 +453,31 @@ impl<'de> serde::de::Deserializer<'de> for &mut Decoder<'de> {
     impl_deserialize!(deserialize_u64: decode_u64 => visit_u64);
     impl_deserialize!(deserialize_u128: decode_u128 => visit_u128);
 
-    impl_deserialize!(deserialize_f32: decode_f32 => visit_f32);
-    impl_deserialize!(deserialize_f64: decode_f64 => visit_f64);
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let value = self.decode_f32()?;
+
+        if self.config.canonical && value.is_nan() && value.to_bits() != f32::NAN.to_bits() {
+            return Error::NonCanonical.into();
+        }
+
+        visitor.visit_f32(value)
+    }
+
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let value = self.decode_f64()?;
+
+        if self.config.canonical && value.is_nan() && value.to_bits() != f64::NAN.to_bits() {
+            return Error::NonCanonical.into();
+        }
+
+        visitor.visit_f64(value)
+    }
 
     fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
@@ -136,44 +507,64 @@ impl<'de> serde::de::Deserializer<'de> for &mut Decoder<'de> {
     where
         V: serde::de::Visitor<'de>,
     {
-        let bytes = self.decode_bytes()?;
-
-        visitor.visit_borrowed_bytes(bytes)
+        match self.decode_bytes()? {
+            Cow::Borrowed(bytes) => visitor.visit_borrowed_bytes(bytes),
+            Cow::Owned(bytes) => visitor.visit_byte_buf(bytes),
+        }
     }
 
     fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: serde::de::Visitor<'de>,
     {
-        let bytes = self.decode_bytes()?;
-
-        visitor.visit_bytes(bytes)
+        match self.decode_bytes()? {
+            Cow::Borrowed(bytes) => visitor.visit_bytes(bytes),
+            Cow::Owned(bytes) => visitor.visit_byte_buf(bytes),
+        }
     }
 
     fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: serde::de::Visitor<'de>,
     {
-        let bytes = self.decode_bytes()?;
+        match self.decode_bytes()? {
+            Cow::Borrowed(bytes) => {
+                let Ok(value) = from_utf8(bytes) else {
+                    return Error::InvalidStr.into();
+                };
 
-        let Ok(value) = from_utf8(bytes) else {
-            return Error::InvalidStr.into();
-        };
+                visitor.visit_borrowed_str(value)
+            }
+            Cow::Owned(bytes) => {
+                let Ok(value) = String::from_utf8(bytes) else {
+                    return Error::InvalidStr.into();
+                };
 
-        visitor.visit_borrowed_str(value)
+                visitor.visit_string(value)
+            }
+        }
     }
 
     fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: serde::de::Visitor<'de>,
     {
-        let bytes = self.decode_bytes()?;
+        match self.decode_bytes()? {
+            Cow::Borrowed(bytes) => {
+                let Ok(value) = from_utf8(bytes) else {
+                    return Error::InvalidStr.into();
+                };
 
-        let Ok(value) = from_utf8(bytes) else {
-            return Error::InvalidStr.into();
-        };
+                visitor.visit_str(value)
+            }
+            Cow::Owned(bytes) => {
+                let Ok(value) = String::from_utf8(bytes) else {
+                    return Error::InvalidStr.into();
+                };
 
-        visitor.visit_str(value)
+                visitor.visit_string(value)
+            }
+        }
     }
 
     fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -196,12 +587,113 @@ impl<'de> serde::de::Deserializer<'de> for &mut Decoder<'de> {
 
     fn deserialize_newtype_struct<V>(
         self,
-        _name: &'static str,
+        name: &'static str,
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: serde::de::Visitor<'de>,
     {
+        if name == lazy::NAME {
+            let frame = self.decode_frame(usize::MAX)?;
+
+            return visitor.visit_seq(Handoff {
+                len: 0,
+                config: self.config,
+                offset: 0,
+                bufs: [frame, &[]],
+                index: 0,
+            });
+        }
+
+        if name == seq_iter::NAME {
+            if self.chunks_len != 0 || self.attachments.is_some() {
+                return Error::NotSupported.into();
+            }
+
+            let len = self.decode_u32()?;
+
+            return visitor.visit_seq(Handoff {
+                len,
+                config: self.config,
+                offset: self.offset(),
+                bufs: [take(&mut self.buf), &[]],
+                index: 0,
+            });
+        }
+
+        if name == indexed::NAME || name == indexed::ELEMENTS {
+            let len = self.decode_u32()?;
+            let table = self.decode_borrowed((len as usize).saturating_mul(size_of::<u32>()))?;
+
+            let data_len = match table.last_chunk() {
+                Some(end) => u32::from_ne_bytes(*end) as usize,
+                None => 0,
+            };
+            let offset = self.offset();
+            let data = self.decode_borrowed(data_len)?;
+
+            if name == indexed::ELEMENTS {
+                let mut elements = Elements {
+                    table: Table {
+                        table,
+                        data,
+                        config: self.config,
+                        offset,
+                    },
+                    attachments: self.attachments,
+                    index: 0,
+                };
+
+                let value = visitor.visit_seq(&mut elements)?;
+
+                if elements.index != len as usize {
+                    return Error::UnconsumedElements.into();
+                }
+
+                return Ok(value);
+            }
+
+            if self.attachments.is_some() {
+                return Error::NotSupported.into();
+            }
+
+            return visitor.visit_seq(Handoff {
+                len,
+                config: self.config,
+                offset,
+                bufs: [table, data],
+                index: 0,
+            });
+        }
+
+        if name == attachment::NAME {
+            if let Some(attachments) = self.attachments {
+                let index = self.decode_u32()?;
+
+                let Some(&bytes) = attachments.get(index as usize) else {
+                    return Error::InvalidAttachment.into();
+                };
+
+                return visitor.visit_newtype_struct(AttachmentDecoder { bytes });
+            }
+        }
+
+        #[cfg(feature = "bytemuck")]
+        if let Some(size) = aligned::element_size(name) {
+            if self.config.aligned {
+                self.decode_pad(size, size_of::<u32>())?;
+            }
+
+            let len = self.decode_u32()?;
+            let bytes = self.decode_slice((len as usize).saturating_mul(size))?;
+
+            if self.config.canonical && !aligned::is_canonical(name, &bytes) {
+                return Error::NonCanonical.into();
+            }
+
+            return visitor.visit_newtype_struct(AlignedDecoder { bytes });
+        }
+
         visitor.visit_newtype_struct(self)
     }
 
@@ -229,7 +721,17 @@ impl<'de> serde::de::Deserializer<'de> for &mut Decoder<'de> {
     where
         V: serde::de::Visitor<'de>,
     {
-        visitor.visit_seq(LimitedDecoder { this: self, len })
+        let mut decoder = LimitedDecoder {
+            this: self,
+            len,
+            prev_key: None,
+        };
+
+        let value = visitor.visit_seq(&mut decoder)?;
+
+        decoder.end()?;
+
+        Ok(value)
     }
 
     fn deserialize_tuple_struct<V>(
@@ -250,10 +752,17 @@ impl<'de> serde::de::Deserializer<'de> for &mut Decoder<'de> {
     {
         let len = self.decode_u32()?;
 
-        visitor.visit_map(LimitedDecoder {
+        let mut decoder = LimitedDecoder {
             this: self,
             len: len as usize,
-        })
+            prev_key: None,
+        };
+
+        let value = visitor.visit_map(&mut decoder)?;
+
+        decoder.end()?;
+
+        Ok(value)
     }
 
     fn deserialize_struct<V>(
@@ -265,7 +774,7 @@ impl<'de> serde::de::Deserializer<'de> for &mut Decoder<'de> {
     where
         V: serde::de::Visitor<'de>,
     {
-        self.deserialize_tuple(fields.len(), visitor)
+        self.decode_struct(fields, visitor)
     }
 
     fn deserialize_enum<V>(
@@ -288,6 +797,46 @@ impl<'de> serde::de::Deserializer<'de> for &mut Decoder<'de> {
 struct LimitedDecoder<'a, 'de> {
     this: &'a mut Decoder<'de>,
     len: usize,
+    prev_key: Option<Cow<'de, [u8]>>,
+}
+
+impl LimitedDecoder<'_, '_> {
+    fn end(self) -> Result<(), Box<Error>> {
+        if self.len != 0 {
+            return Error::UnconsumedElements.into();
+        }
+
+        Ok(())
+    }
+}
+
+impl<'de> LimitedDecoder<'_, 'de> {
+    fn next_canonical_key_seed<T>(&mut self, seed: T) -> Result<T::Value, Box<Error>>
+    where
+        T: serde::de::DeserializeSeed<'de>,
+    {
+        let start = self.this.position();
+        let (buf, chunks, chunks_len) = (self.this.buf, self.this.chunks, self.this.chunks_len);
+
+        let value = seed.deserialize(&mut *self.this)?;
+
+        let len = self.this.position() - start;
+        (self.this.buf, self.this.chunks, self.this.chunks_len) = (buf, chunks, chunks_len);
+
+        let key = self.this.decode_slice(len)?;
+
+        if self
+            .prev_key
+            .as_ref()
+            .is_some_and(|prev_key| *prev_key >= key)
+        {
+            return Error::NonCanonical.into();
+        }
+
+        self.prev_key = Some(key);
+
+        Ok(value)
+    }
 }
 
 impl<'de> serde::de::SeqAccess<'de> for LimitedDecoder<'_, 'de> {
@@ -325,6 +874,15 @@ impl<'de> serde::de::MapAccess<'de> for LimitedDecoder<'_, 'de> {
             Some(len) => {
                 self.len = len;
 
+                #[cfg(feature = "bytemuck")]
+                if self.this.config.canonical && self.this.config.aligned {
+                    self.this.decode_pad(aligned::MAX_ALIGN, 0)?;
+                }
+
+                if self.this.config.canonical {
+                    return self.next_canonical_key_seed(seed).map(Some);
+                }
+
                 let value = seed.deserialize(&mut *self.this)?;
 
                 Ok(Some(value))
@@ -345,6 +903,174 @@ impl<'de> serde::de::MapAccess<'de> for LimitedDecoder<'_, 'de> {
     }
 }
 
+struct StructDecoder<'a, 'de> {
+    this: &'a mut Decoder<'de>,
+    fields: &'static [&'static str],
+    bitmap: Option<Cow<'de, [u8]>>,
+    index: usize,
+    pending: Option<(&'static str, Option<usize>, Selection)>,
+}
+
+impl<'a, 'de> StructDecoder<'a, 'de> {
+    fn is_present(&self, index: usize) -> bool {
+        match &self.bitmap {
+            Some(bitmap) => bitmap[index / 8] & (1 << (index % 8)) != 0,
+            None => true,
+        }
+    }
+
+    fn end(self) -> Result<&'a mut Decoder<'de>, Box<Error>> {
+        if (self.index..self.fields.len()).any(|index| self.is_present(index)) {
+            return Error::UnconsumedElements.into();
+        }
+
+        Ok(self.this)
+    }
+}
+
+impl<'de> serde::de::MapAccess<'de> for StructDecoder<'_, 'de> {
+    type Error = Box<Error>;
+
+    fn next_key_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: serde::de::DeserializeSeed<'de>,
+    {
+        while let Some(&field) = self.fields.get(self.index) {
+            let present = self.is_present(self.index);
+            self.index += 1;
+
+            let len = if self.this.config.field_lengths {
+                Some(self.this.decode_u32()? as usize)
+            } else {
+                None
+            };
+
+            match self.this.select(field) {
+                Some(selection) if present => {
+                    self.pending = Some((field, len, selection));
+
+                    let deserializer =
+                        serde::de::IntoDeserializer::<Self::Error>::into_deserializer(field);
+
+                    let value = seed.deserialize(deserializer)?;
+
+                    return Ok(Some(value));
+                }
+                _ => {
+                    if let Some(len) = len {
+                        self.this.decode_slice(len)?;
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::DeserializeSeed<'de>,
+    {
+        let (field, len, selection) = self.pending.take().expect("Missing key");
+
+        let start = self.this.position();
+        let presence = self.bitmap.is_some();
+        let is_target = self.this.is_target(field);
+
+        let value = self.this.decode_selected(field, selection, |this| {
+            if presence {
+                seed.deserialize(FieldDecoder { this })
+            } else {
+                seed.deserialize(this)
+            }
+        })?;
+
+        if is_target {
+            self.this.located = Some(start..self.this.position());
+        }
+
+        if let Some(len) = len {
+            let Some(rest) = len.checked_sub(self.this.position() - start) else {
+                return Error::InvalidOffset.into();
+            };
+
+            if self.this.config.canonical && rest != 0 {
+                return Error::NonCanonical.into();
+            }
+
+            self.this.decode_slice(rest)?;
+        }
+
+        Ok(value)
+    }
+}
+
+struct FieldDecoder<'a, 'de> {
+    this: &'a mut Decoder<'de>,
+}
+
+macro_rules! forward_deserialize {
+    ($method:ident($($arg:ident: $ty:ty),*)) => {
+        fn $method<V>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: serde::de::Visitor<'de>,
+        {
+            self.this.$method($($arg,)* visitor)
+        }
+    };
+}
+
+impl<'de> serde::de::Deserializer<'de> for FieldDecoder<'_, 'de> {
+    type Error = Box<Error>;
+
+    forward_deserialize!(deserialize_any());
+    forward_deserialize!(deserialize_ignored_any());
+    forward_deserialize!(deserialize_identifier());
+
+    forward_deserialize!(deserialize_i8());
+    forward_deserialize!(deserialize_i16());
+    forward_deserialize!(deserialize_i32());
+    forward_deserialize!(deserialize_i64());
+    forward_deserialize!(deserialize_i128());
+
+    forward_deserialize!(deserialize_u8());
+    forward_deserialize!(deserialize_u16());
+    forward_deserialize!(deserialize_u32());
+    forward_deserialize!(deserialize_u64());
+    forward_deserialize!(deserialize_u128());
+
+    forward_deserialize!(deserialize_f32());
+    forward_deserialize!(deserialize_f64());
+
+    forward_deserialize!(deserialize_bool());
+    forward_deserialize!(deserialize_char());
+    forward_deserialize!(deserialize_bytes());
+    forward_deserialize!(deserialize_byte_buf());
+    forward_deserialize!(deserialize_str());
+    forward_deserialize!(deserialize_string());
+
+    forward_deserialize!(deserialize_unit());
+    forward_deserialize!(deserialize_unit_struct(name: &'static str));
+    forward_deserialize!(deserialize_newtype_struct(name: &'static str));
+    forward_deserialize!(deserialize_seq());
+    forward_deserialize!(deserialize_tuple(len: usize));
+    forward_deserialize!(deserialize_tuple_struct(name: &'static str, len: usize));
+    forward_deserialize!(deserialize_map());
+    forward_deserialize!(deserialize_struct(name: &'static str, fields: &'static [&'static str]));
+    forward_deserialize!(deserialize_enum(name: &'static str, variants: &'static [&'static str]));
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_some(self.this)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
 impl<'de> serde::de::EnumAccess<'de> for &mut Decoder<'de> {
     type Error = Box<Error>;
     type Variant = Self;
@@ -393,6 +1119,61 @@ impl<'de> serde::de::VariantAccess<'de> for &mut Decoder<'de> {
     where
         V: serde::de::Visitor<'de>,
     {
-        serde::de::Deserializer::deserialize_tuple(self, fields.len(), visitor)
+        self.decode_struct(fields, visitor)
+    }
+}
+
+/// Passes the state required to resume decoding to types like [`SeqIter`](crate::SeqIter)
+pub(crate) struct Handoff<'de> {
+    pub(crate) len: u32,
+    pub(crate) config: Config,
+    pub(crate) offset: usize,
+    pub(crate) bufs: [&'de [u8]; 2],
+    index: usize,
+}
+
+impl<'de> Handoff<'de> {
+    pub(crate) fn take<A>(mut seq: A, exp: &dyn serde::de::Expected) -> Result<Self, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let missing = || serde::de::Error::invalid_length(0, exp);
+
+        let len: u32 = seq.next_element()?.ok_or_else(missing)?;
+        let config: u8 = seq.next_element()?.ok_or_else(missing)?;
+        let offset: u64 = seq.next_element()?.ok_or_else(missing)?;
+        let buf0: &'de [u8] = seq.next_element()?.ok_or_else(missing)?;
+        let buf1: &'de [u8] = seq.next_element()?.ok_or_else(missing)?;
+
+        Ok(Self {
+            len,
+            config: Config::from_bits(config),
+            offset: offset as usize,
+            bufs: [buf0, buf1],
+            index: 0,
+        })
+    }
+}
+
+impl<'de> serde::de::SeqAccess<'de> for Handoff<'de> {
+    type Error = Box<Error>;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: serde::de::DeserializeSeed<'de>,
+    {
+        let value = match self.index {
+            0 => seed.deserialize(U32Deserializer::<Self::Error>::new(self.len))?,
+            1 => seed.deserialize(U8Deserializer::<Self::Error>::new(self.config.to_bits()))?,
+            2 => seed.deserialize(U64Deserializer::<Self::Error>::new(self.offset as u64))?,
+            3 | 4 => seed.deserialize(BorrowedBytesDeserializer::<Self::Error>::new(
+                self.bufs[self.index - 3],
+            ))?,
+            _ => return Ok(None),
+        };
+
+        self.index += 1;
+
+        Ok(Some(value))
     }
 }
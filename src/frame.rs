@@ -0,0 +1,97 @@
+use std::io::{ErrorKind, Read};
+
+use super::de::Decoder;
+use super::{Config, Error};
+
+pub struct FrameReader {
+    buf: Vec<u8>,
+    pos: usize,
+    max_len: usize,
+    config: Config,
+}
+
+const CHUNK_LEN: usize = 8 * 1024;
+
+impl FrameReader {
+    pub fn new(max_len: usize) -> Self {
+        Self::with_config(max_len, Config::default())
+    }
+
+    pub fn with_config(max_len: usize, config: Config) -> Self {
+        Self {
+            buf: Vec::new(),
+            pos: 0,
+            max_len,
+            config,
+        }
+    }
+
+    pub fn extend(&mut self, data: &[u8]) {
+        self.compact();
+
+        self.buf.extend_from_slice(data);
+    }
+
+    pub fn read_from<R>(&mut self, reader: &mut R) -> Result<usize, Box<Error>>
+    where
+        R: Read,
+    {
+        self.compact();
+
+        let len = self.buf.len();
+        self.buf.resize(len + CHUNK_LEN, 0);
+
+        loop {
+            match reader.read(&mut self.buf[len..]) {
+                Ok(read) => {
+                    self.buf.truncate(len + read);
+
+                    return Ok(read);
+                }
+                Err(err) if err.kind() == ErrorKind::Interrupted => (),
+                Err(err) => {
+                    self.buf.truncate(len);
+
+                    return Err(err.into());
+                }
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.buf.len()
+    }
+
+    pub fn next_frame(&mut self) -> Result<Option<&[u8]>, Box<Error>> {
+        let mut decoder = Decoder::new(&self.buf[self.pos..]);
+
+        match decoder.decode_frame(self.max_len) {
+            Ok(frame) => {
+                self.pos = self.buf.len() - decoder.remaining().len();
+
+                Ok(Some(frame))
+            }
+            Err(err) if err.is_incomplete() => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn next_value<'a, T>(&'a mut self) -> Result<Option<T>, Box<Error>>
+    where
+        T: serde::de::Deserialize<'a>,
+    {
+        let config = self.config;
+
+        match self.next_frame()? {
+            Some(frame) => config.deserialize_whole(frame).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn compact(&mut self) {
+        if self.pos != 0 {
+            self.buf.drain(..self.pos);
+            self.pos = 0;
+        }
+    }
+}
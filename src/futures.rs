@@ -0,0 +1,93 @@
+use std::future::poll_fn;
+use std::io::{Error as IoError, ErrorKind};
+use std::pin::Pin;
+use std::task::Poll;
+
+use futures_io::{AsyncBufRead, AsyncWrite};
+
+use super::iter::ValueReader;
+use super::{Config, Error};
+
+pub async fn serialize_to_async_writer<W, T>(writer: &mut W, value: &T) -> Result<(), Box<Error>>
+where
+    W: AsyncWrite + Unpin,
+    T: serde::ser::Serialize,
+{
+    serialize_to_async_writer_with_config(writer, value, Config::default()).await
+}
+
+pub async fn serialize_to_async_writer_with_config<W, T>(
+    writer: &mut W,
+    value: &T,
+    config: Config,
+) -> Result<(), Box<Error>>
+where
+    W: AsyncWrite + Unpin,
+    T: serde::ser::Serialize,
+{
+    let buf = config.serialize(value)?;
+
+    write_all(writer, &buf).await?;
+
+    Ok(())
+}
+
+pub async fn deserialize_from_async_reader<R, T>(
+    reader: &mut R,
+    max_len: usize,
+) -> Result<T, Box<Error>>
+where
+    R: AsyncBufRead + Unpin,
+    T: serde::de::DeserializeOwned,
+{
+    deserialize_from_async_reader_with_config(reader, max_len, Config::default()).await
+}
+
+/// Read exactly one value of at most `max_len` bytes, retrying to decode it once the reader provided the bytes known to be missing.
+///
+/// As nothing beyond the value may be consumed, a value much larger than the buffer of the reader can still be decoded
+/// repeatedly, e.g. a long sequence of numbers, so consider framing such values and reading them using [`FrameReader`](crate::FrameReader).
+pub async fn deserialize_from_async_reader_with_config<R, T>(
+    reader: &mut R,
+    max_len: usize,
+    config: Config,
+) -> Result<T, Box<Error>>
+where
+    R: AsyncBufRead + Unpin,
+    T: serde::de::DeserializeOwned,
+{
+    let mut value_reader = ValueReader::new(max_len, config);
+
+    poll_fn(|cx| loop {
+        let available = match Pin::new(&mut *reader).poll_fill_buf(cx) {
+            Poll::Ready(Ok(available)) => available,
+            Poll::Ready(Err(err)) if err.kind() == ErrorKind::Interrupted => continue,
+            Poll::Ready(Err(err)) => return Poll::Ready(Err(err.into())),
+            Poll::Pending => return Poll::Pending,
+        };
+
+        let (value, used) = value_reader.step(available)?;
+        Pin::new(&mut *reader).consume(used);
+
+        if let Some(value) = value {
+            return Poll::Ready(Ok(value));
+        }
+    })
+    .await
+}
+
+async fn write_all<W>(writer: &mut W, mut buf: &[u8]) -> Result<(), IoError>
+where
+    W: AsyncWrite + Unpin,
+{
+    while !buf.is_empty() {
+        match poll_fn(|cx| Pin::new(&mut *writer).poll_write(cx, buf)).await {
+            Ok(0) => return Err(ErrorKind::WriteZero.into()),
+            Ok(written) => buf = &buf[written..],
+            Err(err) if err.kind() == ErrorKind::Interrupted => (),
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(())
+}
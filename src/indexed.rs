@@ -0,0 +1,349 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::ops::{Deref, DerefMut};
+
+use super::de::{Decoder, Handoff};
+use super::{Config, Error};
+
+pub(crate) const NAME: &str = "$serde_mincode::Indexed";
+pub(crate) const ELEMENTS: &str = "$serde_mincode::IndexedElements";
+
+/// A sequence which is encoded with a table of offsets so that it can be accessed using [`IndexedView`]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Indexed<C>(pub C);
+
+impl<C> Deref for Indexed<C> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<C> DerefMut for Indexed<C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<C, T> serde::ser::Serialize for Indexed<C>
+where
+    C: Deref<Target = [T]>,
+    T: serde::ser::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.serialize_newtype_struct(NAME, &*self.0)
+    }
+}
+
+impl<'de, T> serde::de::Deserialize<'de> for Indexed<Vec<T>>
+where
+    T: serde::de::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(ELEMENTS, ElementsVisitor(PhantomData))
+    }
+}
+
+struct ElementsVisitor<T>(PhantomData<fn() -> T>);
+
+impl<'de, T> serde::de::Visitor<'de> for ElementsVisitor<T>
+where
+    T: serde::de::Deserialize<'de>,
+{
+    type Value = Indexed<Vec<T>>;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("indexed sequence")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        <Vec<T> as serde::de::Deserialize>::deserialize(deserializer).map(Indexed)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let mut vec = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+
+        while let Some(elem) = seq.next_element()? {
+            vec.push(elem);
+        }
+
+        Ok(Indexed(vec))
+    }
+}
+
+/// The table of offsets and the data of an encoded [`Indexed`] sequence
+#[derive(Clone, Copy)]
+pub(crate) struct Table<'de> {
+    pub(crate) table: &'de [u8],
+    pub(crate) data: &'de [u8],
+    pub(crate) config: Config,
+    pub(crate) offset: usize,
+}
+
+impl<'de> Table<'de> {
+    pub(crate) fn len(&self) -> usize {
+        self.table.len() / size_of::<u32>()
+    }
+
+    fn end(&self, index: usize) -> usize {
+        let pos = index * size_of::<u32>();
+        let end = self.table[pos..pos + size_of::<u32>()].try_into().unwrap();
+
+        u32::from_ne_bytes(end) as usize
+    }
+
+    pub(crate) fn decoder(&self, index: usize) -> Result<Decoder<'de>, Box<Error>> {
+        let start = if index != 0 { self.end(index - 1) } else { 0 };
+        let end = self.end(index);
+
+        match self.data.get(start..end) {
+            Some(element) => {
+                Ok(Decoder::with_config(element, self.config).with_offset(self.offset + start))
+            }
+            None => Error::InvalidOffset.into(),
+        }
+    }
+}
+
+/// Decodes all elements of an encoded [`Indexed`] sequence in order
+pub(crate) struct Elements<'de> {
+    pub(crate) table: Table<'de>,
+    pub(crate) attachments: Option<&'de [&'de [u8]]>,
+    pub(crate) index: usize,
+}
+
+impl<'de> serde::de::SeqAccess<'de> for Elements<'de> {
+    type Error = Box<Error>;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: serde::de::DeserializeSeed<'de>,
+    {
+        if self.index == self.table.len() {
+            return Ok(None);
+        }
+
+        let mut decoder = self.table.decoder(self.index)?;
+
+        if let Some(attachments) = self.attachments {
+            decoder = decoder.with_attachments(attachments);
+        }
+
+        let value = seed.deserialize(&mut decoder)?;
+        decoder.finish()?;
+
+        self.index += 1;
+
+        Ok(Some(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.table.len() - self.index)
+    }
+}
+
+/// A borrowed sequence encoded using [`Indexed`] which decodes only the requested elements
+///
+/// Decoding with attachments is not supported.
+pub struct IndexedView<'de, T> {
+    table: Table<'de>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'de, T> IndexedView<'de, T> {
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.table.len() == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<Result<T, Box<Error>>>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        if index >= self.len() {
+            return None;
+        }
+
+        Some(self.table.decoder(index).and_then(|mut decoder| {
+            let value = T::deserialize(&mut decoder)?;
+
+            decoder.finish()?;
+            Ok(value)
+        }))
+    }
+}
+
+impl<'de: 'a, 'a, T> serde::de::Deserialize<'de> for IndexedView<'a, T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(NAME, IndexedVisitor(PhantomData, PhantomData))
+    }
+}
+
+struct IndexedVisitor<'a, T>(PhantomData<&'a ()>, PhantomData<fn() -> T>);
+
+impl<'de: 'a, 'a, T> serde::de::Visitor<'de> for IndexedVisitor<'a, T> {
+    type Value = IndexedView<'a, T>;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("indexed sequence")
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let handoff = Handoff::take(seq, &self)?;
+
+        Ok(IndexedView {
+            table: Table {
+                table: handoff.bufs[0],
+                data: handoff.bufs[1],
+                config: handoff.config,
+                offset: handoff.offset,
+            },
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// A map which is encoded as an [`Indexed`] sequence of entries ordered by key so that it can be searched using [`IndexedMapView`]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct IndexedMap<K, V>(pub BTreeMap<K, V>);
+
+impl<K, V> Deref for IndexedMap<K, V> {
+    type Target = BTreeMap<K, V>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<K, V> DerefMut for IndexedMap<K, V> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+struct Entries<'a, K, V>(&'a BTreeMap<K, V>);
+
+impl<K, V> serde::ser::Serialize for Entries<'_, K, V>
+where
+    K: serde::ser::Serialize,
+    V: serde::ser::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.collect_seq(self.0)
+    }
+}
+
+impl<K, V> serde::ser::Serialize for IndexedMap<K, V>
+where
+    K: serde::ser::Serialize,
+    V: serde::ser::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.serialize_newtype_struct(NAME, &Entries(&self.0))
+    }
+}
+
+impl<'de, K, V> serde::de::Deserialize<'de> for IndexedMap<K, V>
+where
+    K: serde::de::Deserialize<'de> + Ord,
+    V: serde::de::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let Indexed(entries) = Indexed::<Vec<(K, V)>>::deserialize(deserializer)?;
+
+        Ok(Self(entries.into_iter().collect()))
+    }
+}
+
+/// A borrowed map encoded using [`IndexedMap`] which is searched by decoding only the visited keys
+///
+/// Decoding with attachments is not supported.
+pub struct IndexedMapView<'de, K, V> {
+    entries: IndexedView<'de, (K, V)>,
+}
+
+impl<'de, K, V> IndexedMapView<'de, K, V> {
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Result<Option<V>, Box<Error>>
+    where
+        K: serde::de::Deserialize<'de> + Borrow<Q>,
+        V: serde::de::Deserialize<'de>,
+        Q: Ord + ?Sized,
+    {
+        let mut lower = 0;
+        let mut upper = self.len();
+
+        while lower < upper {
+            let mid = lower + (upper - lower) / 2;
+
+            let mut decoder = self.entries.table.decoder(mid)?;
+            let key1 = K::deserialize(&mut decoder)?;
+
+            match key1.borrow().cmp(key) {
+                Ordering::Less => lower = mid + 1,
+                Ordering::Greater => upper = mid,
+                Ordering::Equal => {
+                    let value = V::deserialize(&mut decoder)?;
+
+                    decoder.finish()?;
+                    return Ok(Some(value));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl<'de: 'a, 'a, K, V> serde::de::Deserialize<'de> for IndexedMapView<'a, K, V> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let entries = IndexedView::deserialize(deserializer)?;
+
+        Ok(Self { entries })
+    }
+}
@@ -0,0 +1,235 @@
+use std::io::{ErrorKind, Read};
+use std::marker::PhantomData;
+
+use super::de::Decoder;
+use super::{Config, Error};
+
+pub struct DeserializeIter<'de, T> {
+    decoder: Option<Decoder<'de>>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'de, T> DeserializeIter<'de, T> {
+    pub(crate) fn new(buf: &'de [u8], config: Config) -> Self {
+        Self {
+            decoder: Some(Decoder::with_config(buf, config)),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, T> Iterator for DeserializeIter<'de, T>
+where
+    T: serde::de::Deserialize<'de>,
+{
+    type Item = Result<T, Box<Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let decoder = self.decoder.as_mut()?;
+
+        if decoder.is_empty() {
+            return None;
+        }
+
+        decoder.restart();
+
+        let res = T::deserialize(decoder);
+
+        if res.is_err() {
+            self.decoder = None;
+        }
+
+        Some(res)
+    }
+}
+
+pub struct DeserializeReaderIter<R, T> {
+    reader: R,
+    buf: Vec<u8>,
+    pos: usize,
+    config: Config,
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+const CHUNK_LEN: usize = 8 * 1024;
+
+impl<R, T> DeserializeReaderIter<R, T> {
+    pub(crate) fn new(reader: R, config: Config) -> Self {
+        Self {
+            reader,
+            buf: Vec::new(),
+            pos: 0,
+            config,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<R, T> DeserializeReaderIter<R, T>
+where
+    R: Read,
+    T: serde::de::DeserializeOwned,
+{
+    fn read_next(&mut self) -> Result<Option<T>, Box<Error>> {
+        let mut needed = 0;
+
+        loop {
+            if self.pos != self.buf.len() {
+                let mut decoder = Decoder::with_config(&self.buf[self.pos..], self.config);
+
+                match T::deserialize(&mut decoder) {
+                    Ok(value) => {
+                        self.pos = self.buf.len() - decoder.remaining().len();
+
+                        return Ok(Some(value));
+                    }
+                    Err(err) => needed = err.needed().ok_or(err)?,
+                }
+            }
+
+            self.buf.drain(..self.pos);
+            self.pos = 0;
+
+            // Decoding restarts from the beginning of the value, so wait until its buffered part doubled.
+            let min_read = match self.buf.len() {
+                len if len >= CHUNK_LEN => needed.max(len),
+                _ => needed,
+            };
+
+            let read = self.fill(min_read)?;
+
+            if read < needed || (read == 0 && needed == 0) {
+                if self.buf.is_empty() {
+                    return Ok(None);
+                }
+
+                return Error::MissingData(needed - read).into();
+            }
+        }
+    }
+
+    /// Read until at least `min_read` bytes were added or the reader is exhausted.
+    fn fill(&mut self, min_read: usize) -> Result<usize, Box<Error>> {
+        let start = self.buf.len();
+        let mut len = start;
+
+        loop {
+            if len == self.buf.len() {
+                // Length prefixes are untrusted, so grow at most proportionally to the data already read.
+                self.buf.resize(len + len.max(CHUNK_LEN), 0);
+            }
+
+            match self.reader.read(&mut self.buf[len..]) {
+                Ok(0) => break,
+                Ok(read) => {
+                    len += read;
+
+                    if len - start >= min_read {
+                        break;
+                    }
+                }
+                Err(err) if err.kind() == ErrorKind::Interrupted => (),
+                Err(err) => {
+                    self.buf.truncate(len);
+
+                    return Err(err.into());
+                }
+            }
+        }
+
+        self.buf.truncate(len);
+
+        Ok(len - start)
+    }
+}
+
+impl<R, T> Iterator for DeserializeReaderIter<R, T>
+where
+    R: Read,
+    T: serde::de::DeserializeOwned,
+{
+    type Item = Result<T, Box<Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let res = self.read_next().transpose();
+
+        if !matches!(res, Some(Ok(_))) {
+            self.done = true;
+        }
+
+        res
+    }
+}
+
+/// Accumulates the input of a buffered reader until a single value can be decoded without consuming anything beyond it
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+pub(crate) struct ValueReader {
+    buf: Vec<u8>,
+    needed: usize,
+    max_len: usize,
+    config: Config,
+}
+
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+impl ValueReader {
+    pub(crate) fn new(max_len: usize, config: Config) -> Self {
+        Self {
+            buf: Vec::new(),
+            needed: 0,
+            max_len,
+            config,
+        }
+    }
+
+    /// Returns the value if it is complete and the number of available bytes to consume.
+    pub(crate) fn step<T>(&mut self, available: &[u8]) -> Result<(Option<T>, usize), Box<Error>>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        if self.needed != 0 {
+            if available.is_empty() {
+                return Error::MissingData(self.needed).into();
+            }
+
+            // The value is known to span these bytes, so collect them without decoding again.
+            let len = available.len().min(self.needed);
+            self.buf.extend_from_slice(&available[..len]);
+            self.needed -= len;
+
+            return Ok((None, len));
+        }
+
+        let chunks = [&self.buf[..], available];
+        let mut decoder = Decoder::from_chunks_with_config(&chunks, self.config);
+
+        match T::deserialize(&mut decoder) {
+            Ok(value) => {
+                if decoder.position() > self.max_len {
+                    return Error::FrameTooLarge.into();
+                }
+
+                Ok((Some(value), decoder.position() - self.buf.len()))
+            }
+            Err(err) if err.is_incomplete() && !available.is_empty() => {
+                let needed = err.needed().unwrap();
+
+                if self.buf.len() + available.len() + needed > self.max_len {
+                    return Error::FrameTooLarge.into();
+                }
+
+                self.buf.reserve(available.len() + needed);
+                self.buf.extend_from_slice(available);
+                self.needed = needed;
+
+                Ok((None, available.len()))
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
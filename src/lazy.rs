@@ -0,0 +1,124 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use super::de::Handoff;
+use super::{Config, Error, RawMincode};
+
+pub(crate) const NAME: &str = "$serde_mincode::Lazy";
+
+/// A value which is decoded on demand
+///
+/// It is encoded using the [`Config`] of the outer value and prefixed with its length
+/// so that it can be skipped by the outer decoder. When decoded, it keeps that config
+/// to decode the value later. If it contains attachments, decode its
+/// [`encoded`](Lazy::encoded) bytes using [`Config::deserialize_with_attachments`].
+#[derive(Debug, Clone)]
+pub struct Lazy<'a, T>(Repr<'a, T>);
+
+#[derive(Debug, Clone)]
+enum Repr<'a, T> {
+    Value(T),
+    Encoded(RawMincode<'a>, Config),
+}
+
+impl<'a, T> Lazy<'a, T> {
+    pub fn new(value: T) -> Self {
+        Self(Repr::Value(value))
+    }
+
+    pub fn from_encoded(raw: RawMincode<'a>) -> Self {
+        Self::from_encoded_with_config(raw, Config::default())
+    }
+
+    pub fn from_encoded_with_config(raw: RawMincode<'a>, config: Config) -> Self {
+        Self(Repr::Encoded(raw, config))
+    }
+
+    pub fn encoded(&self) -> Option<&RawMincode<'a>> {
+        match &self.0 {
+            Repr::Value(_) => None,
+            Repr::Encoded(raw, _) => Some(raw),
+        }
+    }
+
+    /// Decode the value or clone it if it was not decoded from the input
+    pub fn get<'de>(&'de self) -> Result<T, Box<Error>>
+    where
+        T: serde::de::Deserialize<'de> + Clone,
+    {
+        match &self.0 {
+            Repr::Value(value) => Ok(value.clone()),
+            Repr::Encoded(raw, config) => raw.deserialize_with_config(*config),
+        }
+    }
+
+    /// Decode the value if it was decoded from the input, without requiring `T: Clone`
+    pub fn decode<'de>(&'de self) -> Option<Result<T, Box<Error>>>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        match &self.0 {
+            Repr::Value(_) => None,
+            Repr::Encoded(raw, config) => Some(raw.deserialize_with_config(*config)),
+        }
+    }
+}
+
+impl<T> From<T> for Lazy<'_, T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> serde::ser::Serialize for Lazy<'_, T>
+where
+    T: serde::ser::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        match &self.0 {
+            Repr::Value(value) => serializer.serialize_newtype_struct(NAME, value),
+            Repr::Encoded(raw, _) => raw.serialize(serializer),
+        }
+    }
+}
+
+impl<'de: 'a, 'a, T> serde::de::Deserialize<'de> for Lazy<'a, T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(NAME, LazyVisitor(PhantomData, PhantomData))
+    }
+}
+
+struct LazyVisitor<'a, T>(PhantomData<&'a ()>, PhantomData<fn() -> T>);
+
+impl<'de: 'a, 'a, T> serde::de::Visitor<'de> for LazyVisitor<'a, T> {
+    type Value = Lazy<'a, T>;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("lazy value")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        <RawMincode as serde::de::Deserialize>::deserialize(deserializer).map(Lazy::from_encoded)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let handoff = Handoff::take(seq, &self)?;
+
+        Ok(Lazy::from_encoded_with_config(
+            handoff.bufs[0].into(),
+            handoff.config,
+        ))
+    }
+}
@@ -1,12 +1,58 @@
+#[cfg(feature = "bytemuck")]
+mod aligned;
+pub mod attachment;
+#[cfg(feature = "bytes")]
+pub mod bytes;
+#[cfg(feature = "tokio")]
+mod codec;
 mod de;
+mod frame;
+#[cfg(feature = "futures-io")]
+pub mod futures;
+mod indexed;
+mod iter;
+mod lazy;
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "bytemuck")]
+mod pod;
+mod raw;
+mod seq_iter;
 mod ser;
+mod serializer;
+#[cfg(feature = "tokio")]
+pub mod tokio;
+#[cfg(feature = "bytes")]
+mod vectored;
 
 use std::error::Error as StdError;
 use std::fmt;
+use std::io::{Error as IoError, Read};
 use std::marker::PhantomData;
+use std::mem::size_of;
+use std::ops::Range;
 
-use de::Decoder;
-use ser::Encoder;
+#[cfg(feature = "bytemuck")]
+pub use aligned::{AlignedSlice, Element};
+#[cfg(feature = "tokio")]
+pub use codec::MincodeCodec;
+pub use de::Decoder;
+pub use frame::FrameReader;
+pub use indexed::{Indexed, IndexedMap, IndexedMapView, IndexedView};
+pub use iter::{DeserializeIter, DeserializeReaderIter};
+pub use lazy::Lazy;
+#[cfg(feature = "mmap")]
+pub use mmap::MappedFile;
+#[cfg(feature = "bytemuck")]
+pub use pod::Pod;
+pub use raw::RawMincode;
+pub use seq_iter::SeqIter;
+pub use ser::{Buffer, Encoder, MapEncoder, StructEncoder};
+pub use serializer::Serializer;
+#[cfg(feature = "bytes")]
+pub use vectored::Vectored;
+
+pub type Attachments = Vec<Vec<u8>>;
 
 pub fn serialize<T>(value: &T) -> Result<Vec<u8>, Box<Error>>
 where
@@ -25,6 +71,44 @@ where
     Ok(())
 }
 
+pub fn append<T>(buf: &mut Vec<u8>, value: &T) -> Result<(), Box<Error>>
+where
+    T: serde::ser::Serialize,
+{
+    Config::default().append(buf, value)
+}
+
+pub fn serialize_frame_into<T>(buf: &mut Vec<u8>, value: &T) -> Result<(), Box<Error>>
+where
+    T: serde::ser::Serialize,
+{
+    Config::default().serialize_frame_into(buf, value)
+}
+
+#[cfg(feature = "bytes")]
+pub fn serialize_into_buf<B, T>(buf: &mut B, value: &T) -> Result<(), Box<Error>>
+where
+    B: ::bytes::BufMut,
+    T: serde::ser::Serialize,
+{
+    Config::default().serialize_into_buf(buf, value)
+}
+
+#[cfg(feature = "bytes")]
+pub fn serialize_vectored<T>(value: &T, threshold: usize) -> Result<Vectored, Box<Error>>
+where
+    T: serde::ser::Serialize,
+{
+    Config::default().serialize_vectored(value, threshold)
+}
+
+pub fn serialize_with_attachments<T>(value: &T) -> Result<(Vec<u8>, Attachments), Box<Error>>
+where
+    T: serde::ser::Serialize,
+{
+    Config::default().serialize_with_attachments(value)
+}
+
 pub fn deserialize<'de, T>(buf: &'de [u8]) -> Result<T, Box<Error>>
 where
     T: serde::de::Deserialize<'de>,
@@ -39,32 +123,465 @@ where
     seed.deserialize(&mut Decoder::new(buf))
 }
 
+pub fn deserialize_with_attachments<'de, T>(
+    buf: &'de [u8],
+    attachments: &'de [&'de [u8]],
+) -> Result<T, Box<Error>>
+where
+    T: serde::de::Deserialize<'de>,
+{
+    Config::default().deserialize_with_attachments(buf, attachments)
+}
+
+pub fn locate<'de, T>(buf: &'de [u8], path: &'de str) -> Result<Range<usize>, Box<Error>>
+where
+    T: serde::de::Deserialize<'de>,
+{
+    Config::default().locate::<T>(buf, path)
+}
+
+pub fn patch<T, V>(buf: &mut [u8], path: &str, value: &V) -> Result<(), Box<Error>>
+where
+    T: serde::de::DeserializeOwned,
+    V: serde::ser::Serialize,
+{
+    Config::default().patch::<T, V>(buf, path, value)
+}
+
+pub fn patch_at<V>(buf: &mut [u8], range: Range<usize>, value: &V) -> Result<(), Box<Error>>
+where
+    V: serde::ser::Serialize,
+{
+    Config::default().patch_at(buf, range, value)
+}
+
+pub fn deserialize_chunks<'de, T>(chunks: &'de [&'de [u8]]) -> Result<T, Box<Error>>
+where
+    T: serde::de::Deserialize<'de>,
+{
+    Config::default().deserialize_chunks(chunks)
+}
+
+#[cfg(feature = "bytes")]
+pub fn deserialize_from_bytes<'de, T>(buf: &'de ::bytes::Bytes) -> Result<T, Box<Error>>
+where
+    T: serde::de::Deserialize<'de>,
+{
+    Config::default().deserialize_from_bytes(buf)
+}
+
+pub fn deserialize_iter<'de, T>(buf: &'de [u8]) -> DeserializeIter<'de, T>
+where
+    T: serde::de::Deserialize<'de>,
+{
+    Config::default().deserialize_iter(buf)
+}
+
+pub fn deserialize_iter_from_reader<R, T>(reader: R) -> DeserializeReaderIter<R, T>
+where
+    R: Read,
+    T: serde::de::DeserializeOwned,
+{
+    Config::default().deserialize_iter_from_reader(reader)
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Config {
+    presence_bitmap: bool,
+    field_lengths: bool,
+    canonical: bool,
+    #[cfg(feature = "bytemuck")]
+    aligned: bool,
+}
+
+impl Config {
+    /// Prefix structs with a bitmap of present fields, omitting skipped fields and `None` values.
+    ///
+    /// Fields with `#[serde(skip_serializing)]` are not reported to the serializer and shift the positions of
+    /// all later fields, which breaks decoding. Use `skip_serializing_if` with a function returning `true` instead.
+    pub fn presence_bitmap(mut self, enabled: bool) -> Self {
+        self.presence_bitmap = enabled;
+        self
+    }
+
+    /// Produce a unique encoding for each value by sorting map entries by their encoded keys and normalising NaNs.
+    ///
+    /// When decoding, reject input which is not in this canonical form, including trailing data after the value.
+    /// Sets are indistinguishable from sequences for Serde and hence encoded in iteration order, so use `BTreeSet` instead of `HashSet`.
+    pub fn canonical(mut self, enabled: bool) -> Self {
+        self.canonical = enabled;
+        self
+    }
+
+    /// Prefix struct fields with their length so that they can be skipped, c.f. [`Config::deserialize_projected`].
+    pub fn field_lengths(mut self, enabled: bool) -> Self {
+        self.field_lengths = enabled;
+        self
+    }
+
+    /// Align the elements of [`AlignedSlice`] and [`Pod`] relative to the start of the encoded value.
+    ///
+    /// Note that presence bitmaps are inserted after their fields are encoded and will therefore shift slices nested within structs.
+    /// Together with [`Config::canonical`], map entries are padded to the maximum alignment as they are encoded separately.
+    #[cfg(feature = "bytemuck")]
+    pub fn aligned(mut self, enabled: bool) -> Self {
+        self.aligned = enabled;
+        self
+    }
+
+    pub(crate) fn to_bits(self) -> u8 {
+        #[allow(unused_mut)]
+        let mut bits = self.presence_bitmap as u8
+            | (self.field_lengths as u8) << 2
+            | (self.canonical as u8) << 3;
+
+        #[cfg(feature = "bytemuck")]
+        {
+            bits |= (self.aligned as u8) << 1;
+        }
+
+        bits
+    }
+
+    pub(crate) fn from_bits(bits: u8) -> Self {
+        Self {
+            presence_bitmap: bits & 1 != 0,
+            field_lengths: bits & 4 != 0,
+            canonical: bits & 8 != 0,
+            #[cfg(feature = "bytemuck")]
+            aligned: bits & 2 != 0,
+        }
+    }
+
+    pub fn serialize<T>(self, value: &T) -> Result<Vec<u8>, Box<Error>>
+    where
+        T: serde::ser::Serialize,
+    {
+        let mut buf = Vec::new();
+        self.serialize_into(&mut buf, value)?;
+        Ok(buf)
+    }
+
+    pub fn serialize_into<T>(self, buf: &mut Vec<u8>, value: &T) -> Result<(), Box<Error>>
+    where
+        T: serde::ser::Serialize,
+    {
+        value.serialize(Encoder::with_config(buf, self))?;
+        Ok(())
+    }
+
+    /// Append an element to the sequence making up the whole buffer, incrementing its length prefix.
+    ///
+    /// An empty buffer is treated as an empty sequence.
+    pub fn append<T>(self, buf: &mut Vec<u8>, value: &T) -> Result<(), Box<Error>>
+    where
+        T: serde::ser::Serialize,
+    {
+        let start = buf.len();
+
+        if start == 0 {
+            buf.extend_from_slice(&0_u32.to_ne_bytes());
+        }
+
+        let Some((len, _)) = buf.split_first_chunk() else {
+            return Error::MissingData(size_of::<u32>() - start).into();
+        };
+
+        let Some(len) = u32::from_ne_bytes(*len).checked_add(1) else {
+            return Error::InvalidLength.into();
+        };
+
+        if let Err(err) = value.serialize(Encoder::with_config(buf, self).with_start(0)) {
+            buf.truncate(start);
+            return Err(err);
+        }
+
+        buf[..size_of::<u32>()].copy_from_slice(&len.to_ne_bytes());
+        Ok(())
+    }
+
+    /// Prefix the encoded value with its length so that it can be read back using [`FrameReader`].
+    pub fn serialize_frame_into<T>(self, buf: &mut Vec<u8>, value: &T) -> Result<(), Box<Error>>
+    where
+        T: serde::ser::Serialize,
+    {
+        Encoder::with_config(buf, self).serialize_frame(value)?;
+        Ok(())
+    }
+
+    #[cfg(feature = "bytes")]
+    pub fn serialize_into_buf<B, T>(self, buf: &mut B, value: &T) -> Result<(), Box<Error>>
+    where
+        B: ::bytes::BufMut,
+        T: serde::ser::Serialize,
+    {
+        Serializer::with_thread_local(|serializer| {
+            buf.put_slice(serializer.serialize_with(self, value)?);
+            Ok(())
+        })
+    }
+
+    #[cfg(feature = "bytes")]
+    pub fn serialize_vectored<T>(self, value: &T, threshold: usize) -> Result<Vectored, Box<Error>>
+    where
+        T: serde::ser::Serialize,
+    {
+        let mut buf = Vectored::new(threshold);
+        bytes::with_vectored(|| value.serialize(Encoder::with_config(&mut buf, self)))?;
+        Ok(buf)
+    }
+
+    pub fn serialize_with_attachments<T>(
+        self,
+        value: &T,
+    ) -> Result<(Vec<u8>, Attachments), Box<Error>>
+    where
+        T: serde::ser::Serialize,
+    {
+        let mut buf = Vec::new();
+        let mut attachments = Vec::new();
+        value.serialize(Encoder::with_config(&mut buf, self).with_attachments(&mut attachments))?;
+        Ok((buf, attachments))
+    }
+
+    pub fn deserialize<'de, T>(self, buf: &'de [u8]) -> Result<T, Box<Error>>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        self.deserialize_seed(buf, PhantomData)
+    }
+
+    pub fn deserialize_seed<'de, T>(self, buf: &'de [u8], seed: T) -> Result<T::Value, Box<Error>>
+    where
+        T: serde::de::DeserializeSeed<'de>,
+    {
+        let mut decoder = Decoder::with_config(buf, self);
+        let value = seed.deserialize(&mut decoder)?;
+
+        decoder.finish()?;
+        Ok(value)
+    }
+
+    /// Deserialize a value which must span the whole buffer, e.g. because it was framed.
+    pub(crate) fn deserialize_whole<'de, T>(self, buf: &'de [u8]) -> Result<T, Box<Error>>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        let mut decoder = Decoder::with_config(buf, self);
+        let value = T::deserialize(&mut decoder)?;
+
+        if !decoder.is_empty() {
+            return Error::TrailingData.into();
+        }
+
+        Ok(value)
+    }
+
+    pub fn deserialize_with_attachments<'de, T>(
+        self,
+        buf: &'de [u8],
+        attachments: &'de [&'de [u8]],
+    ) -> Result<T, Box<Error>>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        let mut decoder = Decoder::with_config(buf, self).with_attachments(attachments);
+        let value = T::deserialize(&mut decoder)?;
+
+        decoder.finish()?;
+        Ok(value)
+    }
+
+    /// Decode only the struct fields selected by the given dot-separated paths, skipping all others.
+    ///
+    /// Requires [`Config::field_lengths`] and fields which are not selected must be optional or have defaults.
+    pub fn deserialize_projected<'de, T>(
+        self,
+        buf: &'de [u8],
+        paths: &'de [&'de str],
+    ) -> Result<T, Box<Error>>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        if !self.field_lengths {
+            return Error::NotSupported.into();
+        }
+
+        let mut decoder = Decoder::with_config(buf, self).with_projection(paths);
+        let value = T::deserialize(&mut decoder)?;
+
+        decoder.finish()?;
+        Ok(value)
+    }
+
+    /// Locate the struct field at the given dot-separated path so that it can be overwritten using [`Config::patch_at`].
+    pub fn locate<'de, T>(self, buf: &'de [u8], path: &'de str) -> Result<Range<usize>, Box<Error>>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        let mut decoder = Decoder::with_config(buf, self).with_target(path);
+        T::deserialize(&mut decoder)?;
+
+        match decoder.located() {
+            Some(range) => Ok(range),
+            None => Error::UnknownPath.into(),
+        }
+    }
+
+    /// Overwrite the struct field at the given path in place, c.f. [`Config::patch_at`].
+    ///
+    /// The buffer is restored if it does not decode with the field spanning the same bytes afterwards.
+    pub fn patch<T, V>(self, buf: &mut [u8], path: &str, value: &V) -> Result<(), Box<Error>>
+    where
+        T: serde::de::DeserializeOwned,
+        V: serde::ser::Serialize,
+    {
+        let range = self.locate::<T>(buf, path)?;
+        let prev = buf[range.clone()].to_vec();
+
+        self.patch_at(buf, range.clone(), value)?;
+
+        match self.locate::<T>(buf, path) {
+            Ok(located) if located == range => Ok(()),
+            res => {
+                buf[range].copy_from_slice(&prev);
+
+                match res {
+                    Err(err) => Err(err),
+                    Ok(_) => Error::NotSupported.into(),
+                }
+            }
+        }
+    }
+
+    /// Overwrite a field in place if its new value has the same encoded length, e.g. because it is a fixed-width number.
+    ///
+    /// The new value is not checked against the type of the field.
+    pub fn patch_at<V>(
+        self,
+        buf: &mut [u8],
+        range: Range<usize>,
+        value: &V,
+    ) -> Result<(), Box<Error>>
+    where
+        V: serde::ser::Serialize,
+    {
+        let mut bytes = Vec::new();
+        Encoder::with_config(&mut bytes, self).serialize_field_value(value)?;
+
+        let Some(field) = buf.get_mut(range) else {
+            return Error::InvalidOffset.into();
+        };
+
+        if field.len() != bytes.len() {
+            return Error::NotSupported.into();
+        }
+
+        field.copy_from_slice(&bytes);
+        Ok(())
+    }
+
+    pub fn deserialize_chunks<'de, T>(self, chunks: &'de [&'de [u8]]) -> Result<T, Box<Error>>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        let mut decoder = Decoder::from_chunks_with_config(chunks, self);
+        let value = T::deserialize(&mut decoder)?;
+
+        decoder.finish()?;
+        Ok(value)
+    }
+
+    #[cfg(feature = "bytes")]
+    pub fn deserialize_from_bytes<'de, T>(self, buf: &'de ::bytes::Bytes) -> Result<T, Box<Error>>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        bytes::with_source(buf, || self.deserialize(buf))
+    }
+
+    pub fn deserialize_iter<'de, T>(self, buf: &'de [u8]) -> DeserializeIter<'de, T>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        DeserializeIter::new(buf, self)
+    }
+
+    pub fn deserialize_iter_from_reader<R, T>(self, reader: R) -> DeserializeReaderIter<R, T>
+    where
+        R: Read,
+        T: serde::de::DeserializeOwned,
+    {
+        DeserializeReaderIter::new(reader, self)
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
-    MissingData,
+    MissingData(usize),
     NotSupported,
     InvalidBool,
     InvalidChar,
     InvalidStr,
     InvalidOption,
+    UnconsumedElements,
+    FrameTooLarge,
+    TrailingData,
+    InvalidAttachment,
+    InvalidOffset,
+    UnknownPath,
+    NonCanonical,
+    InvalidLength,
+    Io(IoError),
     Custom(String),
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::MissingData => fmt.write_str("missing data"),
+            Self::MissingData(needed) => write!(fmt, "missing data: {needed} more bytes needed"),
             Self::NotSupported => fmt.write_str("not supported"),
             Self::InvalidBool => fmt.write_str("invalid bool"),
             Self::InvalidChar => fmt.write_str("invalid char"),
             Self::InvalidStr => fmt.write_str("invalid str"),
             Self::InvalidOption => fmt.write_str("invalid option"),
+            Self::UnconsumedElements => fmt.write_str("unconsumed elements"),
+            Self::FrameTooLarge => fmt.write_str("frame too large"),
+            Self::TrailingData => fmt.write_str("trailing data"),
+            Self::InvalidAttachment => fmt.write_str("invalid attachment"),
+            Self::InvalidOffset => fmt.write_str("invalid offset"),
+            Self::UnknownPath => fmt.write_str("unknown path"),
+            Self::NonCanonical => fmt.write_str("non-canonical encoding"),
+            Self::InvalidLength => fmt.write_str("invalid length"),
+            Self::Io(err) => write!(fmt, "io: {err}"),
             Self::Custom(msg) => write!(fmt, "custom: {msg}"),
         }
     }
 }
 
-impl StdError for Error {}
+impl Error {
+    /// Whether decoding failed only because the input ended prematurely.
+    pub fn is_incomplete(&self) -> bool {
+        matches!(self, Self::MissingData(_))
+    }
+
+    /// The minimum number of additional bytes required if the input is incomplete.
+    pub fn needed(&self) -> Option<usize> {
+        match self {
+            Self::MissingData(needed) => Some(*needed),
+            _ => None,
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
 impl<T> From<Error> for Result<T, Box<Error>> {
     #[cold]
@@ -73,6 +590,13 @@ impl<T> From<Error> for Result<T, Box<Error>> {
     }
 }
 
+impl From<IoError> for Box<Error> {
+    #[cold]
+    fn from(err: IoError) -> Self {
+        Box::new(Error::Io(err))
+    }
+}
+
 impl serde::ser::Error for Box<Error> {
     #[cold]
     fn custom<T>(msg: T) -> Self
@@ -0,0 +1,60 @@
+use std::fs::File;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use super::de::Decoder;
+use super::iter::DeserializeIter;
+use super::{Config, Error};
+
+pub struct MappedFile {
+    mmap: Mmap,
+    config: Config,
+}
+
+impl MappedFile {
+    /// # Safety
+    ///
+    /// The file must not be modified while it is mapped, c.f. [`Mmap::map`].
+    pub unsafe fn open<P>(path: P) -> Result<Self, Box<Error>>
+    where
+        P: AsRef<Path>,
+    {
+        Self::open_with_config(path, Config::default())
+    }
+
+    /// # Safety
+    ///
+    /// The file must not be modified while it is mapped, c.f. [`Mmap::map`].
+    pub unsafe fn open_with_config<P>(path: P, config: Config) -> Result<Self, Box<Error>>
+    where
+        P: AsRef<Path>,
+    {
+        let file = File::open(path)?;
+        let mmap = Mmap::map(&file)?;
+
+        Ok(Self { mmap, config })
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.mmap
+    }
+
+    pub fn deserialize<'de, T>(&'de self) -> Result<T, Box<Error>>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        let mut decoder = Decoder::with_config(&self.mmap, self.config);
+        let value = T::deserialize(&mut decoder)?;
+
+        decoder.finish()?;
+        Ok(value)
+    }
+
+    pub fn deserialize_iter<'de, T>(&'de self) -> DeserializeIter<'de, T>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        DeserializeIter::new(&self.mmap, self.config)
+    }
+}
@@ -0,0 +1,65 @@
+use std::ops::{Deref, DerefMut};
+
+use super::aligned::{AlignedSlice, Bulk, Element};
+
+/// A sequence of numbers which is encoded and decoded by copying its memory in bulk
+///
+/// The encoding is the same as for `Vec<T>` unless [`Config::aligned`](crate::Config::aligned) is enabled,
+/// which prefixes it with the length of the padding and the padding itself, like for [`AlignedSlice`].
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Pod<C>(pub C);
+
+impl<C> Deref for Pod<C> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<C> DerefMut for Pod<C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<C, T> serde::ser::Serialize for Pod<C>
+where
+    C: Deref<Target = [T]>,
+    T: Element + serde::ser::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.serialize_newtype_struct(T::NAME, &Bulk(&self.0))
+    }
+}
+
+impl<'de, T> serde::de::Deserialize<'de> for Pod<Vec<T>>
+where
+    T: Element + serde::de::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let slice = AlignedSlice::<T>::deserialize(deserializer)?;
+
+        Ok(Self(slice.into_owned()))
+    }
+}
+
+impl<'de, T> serde::de::Deserialize<'de> for Pod<Box<[T]>>
+where
+    T: Element + serde::de::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let Pod(vec) = Pod::<Vec<T>>::deserialize(deserializer)?;
+
+        Ok(Self(vec.into_boxed_slice()))
+    }
+}
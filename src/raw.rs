@@ -0,0 +1,108 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::marker::PhantomData;
+
+use super::{Config, Error};
+
+/// An encoded value which is embedded verbatim using a length prefix
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RawMincode<'a>(Cow<'a, [u8]>);
+
+impl<'a> RawMincode<'a> {
+    pub fn from_value<T>(value: &T) -> Result<Self, Box<Error>>
+    where
+        T: serde::ser::Serialize,
+    {
+        Self::from_value_with_config(value, Config::default())
+    }
+
+    pub fn from_value_with_config<T>(value: &T, config: Config) -> Result<Self, Box<Error>>
+    where
+        T: serde::ser::Serialize,
+    {
+        config.serialize(value).map(Self::from)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn into_owned(self) -> RawMincode<'static> {
+        RawMincode(Cow::Owned(self.0.into_owned()))
+    }
+
+    pub fn deserialize<'de, T>(&'de self) -> Result<T, Box<Error>>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        self.deserialize_with_config(Config::default())
+    }
+
+    pub fn deserialize_with_config<'de, T>(&'de self, config: Config) -> Result<T, Box<Error>>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        config.deserialize(&self.0)
+    }
+}
+
+impl<'a> From<&'a [u8]> for RawMincode<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self(Cow::Borrowed(bytes))
+    }
+}
+
+impl From<Vec<u8>> for RawMincode<'_> {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(Cow::Owned(bytes))
+    }
+}
+
+impl serde::ser::Serialize for RawMincode<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+impl<'de: 'a, 'a> serde::de::Deserialize<'de> for RawMincode<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(RawVisitor(PhantomData))
+    }
+}
+
+struct RawVisitor<'a>(PhantomData<&'a ()>);
+
+impl<'de: 'a, 'a> serde::de::Visitor<'de> for RawVisitor<'a> {
+    type Value = RawMincode<'a>;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("raw mincode")
+    }
+
+    fn visit_borrowed_bytes<E>(self, bytes: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(RawMincode(Cow::Borrowed(bytes)))
+    }
+
+    fn visit_bytes<E>(self, bytes: &[u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(RawMincode(Cow::Owned(bytes.to_owned())))
+    }
+
+    fn visit_byte_buf<E>(self, bytes: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(RawMincode(Cow::Owned(bytes)))
+    }
+}
@@ -0,0 +1,80 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use super::de::{Decoder, Handoff};
+use super::Error;
+
+pub(crate) const NAME: &str = "$serde_mincode::SeqIter";
+
+/// A sequence which is decoded element by element while iterating
+///
+/// It decodes the encoding of a `Vec<T>`. As the elements are not skipped, this must be the last value of the input.
+/// To follow it by other values, encode a [`Lazy<Vec<T>>`](crate::Lazy) instead and decode it as `Lazy<SeqIter<T>>`.
+/// Decoding from chunks or with attachments is not supported.
+pub struct SeqIter<'de, T> {
+    decoder: Decoder<'de>,
+    len: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'de, T> Iterator for SeqIter<'de, T>
+where
+    T: serde::de::Deserialize<'de>,
+{
+    type Item = Result<T, Box<Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.len = self.len.checked_sub(1)?;
+
+        let mut res = T::deserialize(&mut self.decoder);
+
+        if res.is_ok() && self.len == 0 && !self.decoder.is_empty() {
+            res = Error::UnconsumedElements.into();
+        }
+
+        if res.is_err() {
+            self.len = 0;
+        }
+
+        Some(res)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'de, T> ExactSizeIterator for SeqIter<'de, T> where T: serde::de::Deserialize<'de> {}
+
+impl<'de: 'a, 'a, T> serde::de::Deserialize<'de> for SeqIter<'a, T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(NAME, SeqIterVisitor(PhantomData, PhantomData))
+    }
+}
+
+struct SeqIterVisitor<'a, T>(PhantomData<&'a ()>, PhantomData<fn() -> T>);
+
+impl<'de: 'a, 'a, T> serde::de::Visitor<'de> for SeqIterVisitor<'a, T> {
+    type Value = SeqIter<'a, T>;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("sequence iterator")
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let handoff = Handoff::take(seq, &self)?;
+
+        Ok(SeqIter {
+            decoder: Decoder::with_config(handoff.bufs[0], handoff.config)
+                .with_offset(handoff.offset),
+            len: handoff.len as usize,
+            _marker: PhantomData,
+        })
+    }
+}
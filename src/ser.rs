@@ -1,35 +1,233 @@
-use super::Error;
+use std::mem::size_of;
 
-pub struct Encoder<'a> {
-    buf: &'a mut Vec<u8>,
+use serde::ser::Impossible;
+
+#[cfg(feature = "bytemuck")]
+use super::aligned;
+use super::{attachment, indexed, lazy, Config, Error};
+
+pub trait Buffer {
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn extend_from_slice(&mut self, bytes: &[u8]);
+
+    fn insert(&mut self, pos: usize, bytes: &[u8]);
+
+    fn overwrite(&mut self, pos: usize, bytes: &[u8]);
 }
 
-impl<'a> Encoder<'a> {
-    pub fn new(buf: &'a mut Vec<u8>) -> Self {
-        Self { buf }
+impl Buffer for Vec<u8> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        Vec::extend_from_slice(self, bytes);
+    }
+
+    fn insert(&mut self, pos: usize, bytes: &[u8]) {
+        self.splice(pos..pos, bytes.iter().copied());
+    }
+
+    fn overwrite(&mut self, pos: usize, bytes: &[u8]) {
+        self[pos..pos + bytes.len()].copy_from_slice(bytes);
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Buffer for bytes::BytesMut {
+    fn len(&self) -> usize {
+        bytes::BytesMut::len(self)
+    }
+
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        bytes::BytesMut::extend_from_slice(self, bytes);
+    }
+
+    fn insert(&mut self, pos: usize, bytes: &[u8]) {
+        bytes::BytesMut::extend_from_slice(self, bytes);
+        self[pos..].rotate_right(bytes.len());
+    }
+
+    fn overwrite(&mut self, pos: usize, bytes: &[u8]) {
+        self[pos..pos + bytes.len()].copy_from_slice(bytes);
+    }
+}
+
+pub struct Encoder<'a, B = Vec<u8>> {
+    buf: &'a mut B,
+    start: usize,
+    attachments: Option<&'a mut Vec<Vec<u8>>>,
+    config: Config,
+}
+
+impl<'a, B> Encoder<'a, B>
+where
+    B: Buffer,
+{
+    pub fn new(buf: &'a mut B) -> Self {
+        Self::with_config(buf, Config::default())
+    }
+
+    pub fn with_config(buf: &'a mut B, config: Config) -> Self {
+        let start = buf.len();
+
+        Self {
+            buf,
+            start,
+            attachments: None,
+            config,
+        }
+    }
+
+    /// Continue a value which was started at the given position of the buffer.
+    pub(crate) fn with_start(mut self, start: usize) -> Self {
+        self.start = start;
+        self
+    }
+
+    pub(crate) fn with_attachments(mut self, attachments: &'a mut Vec<Vec<u8>>) -> Self {
+        self.attachments = Some(attachments);
+        self
+    }
+
+    pub fn bytes_written(&self) -> usize {
+        self.buf.len() - self.start
     }
 
-    fn reborrow(&mut self) -> Encoder<'_> {
+    fn reborrow(&mut self) -> Encoder<'_, B> {
         Encoder {
             buf: &mut *self.buf,
+            start: self.start,
+            attachments: self.attachments.as_deref_mut(),
+            config: self.config,
         }
     }
 
     fn serialize_len(self, len: usize) -> Result<Self, Box<Error>> {
         serde::ser::Serializer::serialize_u32(self, len.try_into().expect("Excessive length"))
     }
+
+    pub(crate) fn serialize_frame<T>(self, value: &T) -> Result<Self, Box<Error>>
+    where
+        T: serde::ser::Serialize + ?Sized,
+    {
+        let start = self.buf.len();
+        let mut this = self.serialize_len(0)?;
+        let header = this.buf.len();
+
+        let mut inner = this.reborrow();
+        inner.start = header;
+        value.serialize(inner)?;
+
+        let len = this.buf.len() - header;
+        let len: u32 = len.try_into().expect("Excessive length");
+        this.buf.overwrite(start, &len.to_ne_bytes());
+
+        Ok(this)
+    }
+
+    /// Encode a value like a struct field, i.e. without an option tag if presence bitmaps are enabled.
+    pub(crate) fn serialize_field_value<T>(self, value: &T) -> Result<Self, Box<Error>>
+    where
+        T: serde::ser::Serialize + ?Sized,
+    {
+        if !self.config.presence_bitmap {
+            return value.serialize(self);
+        }
+
+        let mut present = true;
+
+        let this = value.serialize(FieldEncoder {
+            this: self,
+            present: &mut present,
+        })?;
+
+        if !present {
+            return Error::NotSupported.into();
+        }
+
+        Ok(this)
+    }
+
+    /// Pad such that the value following the pad and another `rest` bytes is aligned to `size`.
+    #[cfg(feature = "bytemuck")]
+    fn serialize_pad(&mut self, size: usize, rest: usize) {
+        let offset = self.bytes_written() + 1 + rest;
+        let pad = (size - offset % size) % size;
+
+        self.buf.extend_from_slice(&[pad as u8]);
+        self.buf.extend_from_slice(&[0; aligned::MAX_ALIGN][..pad]);
+    }
+
+    #[cfg(feature = "bytemuck")]
+    fn serialize_bulk<T>(mut self, size: usize, value: &T) -> Result<Self, Box<Error>>
+    where
+        T: serde::ser::Serialize + ?Sized,
+    {
+        if self.config.aligned {
+            self.serialize_pad(size, size_of::<u32>());
+        }
+
+        if self.config.canonical {
+            return value.serialize(self);
+        }
+
+        let start = self.buf.len();
+        let this = aligned::with_bulk(|| value.serialize(self))?;
+
+        let len = (this.buf.len() - start - size_of::<u32>()) / size;
+        let len: u32 = len.try_into().expect("Excessive length");
+        this.buf.overwrite(start, &len.to_ne_bytes());
+
+        Ok(this)
+    }
+
+    fn serialize_attachment<T>(mut self, value: &T) -> Result<Self, Box<Error>>
+    where
+        T: serde::ser::Serialize + ?Sized,
+    {
+        let Some(attachments) = &mut self.attachments else {
+            return value.serialize(self);
+        };
+
+        let mut bytes = Vec::new();
+        value.serialize(Encoder::new(&mut bytes))?;
+
+        let Some((len, _)) = bytes.split_first_chunk() else {
+            return Error::NotSupported.into();
+        };
+
+        if u32::from_ne_bytes(*len) as usize != bytes.len() - size_of::<u32>() {
+            return Error::NotSupported.into();
+        }
+
+        bytes.drain(..size_of::<u32>());
+
+        let index = attachments.len();
+        attachments.push(bytes);
+
+        self.serialize_len(index)
+    }
 }
 
 macro_rules! impl_serialize {
     ($method:ident($ty:ty)) => {
         fn $method(self, value: $ty) -> Result<Self::Ok, Self::Error> {
-            self.buf.extend(&value.to_ne_bytes());
+            self.buf.extend_from_slice(&value.to_ne_bytes());
             Ok(self)
         }
     };
 }
 
-impl serde::ser::Serializer for Encoder<'_> {
+impl<'a, B> serde::ser::Serializer for Encoder<'a, B>
+where
+    B: Buffer,
+{
     type Ok = Self;
     type Error = Box<Error>;
 
@@ -37,9 +235,9 @@ impl serde::ser::Serializer for Encoder<'_> {
     type SerializeTuple = Self;
     type SerializeTupleStruct = Self;
     type SerializeTupleVariant = Self;
-    type SerializeMap = Self;
-    type SerializeStruct = Self;
-    type SerializeStructVariant = Self;
+    type SerializeMap = MapEncoder<'a, B>;
+    type SerializeStruct = StructEncoder<'a, B>;
+    type SerializeStructVariant = StructEncoder<'a, B>;
 
     impl_serialize!(serialize_i8(i8));
     impl_serialize!(serialize_i16(i16));
@@ -53,8 +251,27 @@ impl serde::ser::Serializer for Encoder<'_> {
     impl_serialize!(serialize_u64(u64));
     impl_serialize!(serialize_u128(u128));
 
-    impl_serialize!(serialize_f32(f32));
-    impl_serialize!(serialize_f64(f64));
+    fn serialize_f32(self, value: f32) -> Result<Self::Ok, Self::Error> {
+        let value = if self.config.canonical && value.is_nan() {
+            f32::NAN
+        } else {
+            value
+        };
+
+        self.buf.extend_from_slice(&value.to_ne_bytes());
+        Ok(self)
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<Self::Ok, Self::Error> {
+        let value = if self.config.canonical && value.is_nan() {
+            f64::NAN
+        } else {
+            value
+        };
+
+        self.buf.extend_from_slice(&value.to_ne_bytes());
+        Ok(self)
+    }
 
     fn serialize_bool(self, value: bool) -> Result<Self::Ok, Self::Error> {
         self.serialize_u8(value as u8)
@@ -66,7 +283,7 @@ impl serde::ser::Serializer for Encoder<'_> {
 
     fn serialize_bytes(self, value: &[u8]) -> Result<Self::Ok, Self::Error> {
         let this = self.serialize_len(value.len())?;
-        this.buf.extend(value);
+        this.buf.extend_from_slice(value);
         Ok(this)
     }
 
@@ -83,7 +300,7 @@ impl serde::ser::Serializer for Encoder<'_> {
     }
 
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
-        self.buf.push(0);
+        self.buf.extend_from_slice(&[0]);
         Ok(self)
     }
 
@@ -91,7 +308,7 @@ impl serde::ser::Serializer for Encoder<'_> {
     where
         T: serde::Serialize + ?Sized,
     {
-        self.buf.push(1);
+        self.buf.extend_from_slice(&[1]);
         value.serialize(self)
     }
 
@@ -124,7 +341,8 @@ impl serde::ser::Serializer for Encoder<'_> {
 
     fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
         let this = self.serialize_len(len.expect("Missing length"))?;
-        Ok(this)
+        let entries = this.config.canonical.then(Vec::new);
+        Ok(MapEncoder { this, entries })
     }
 
     fn serialize_struct(
@@ -132,7 +350,7 @@ impl serde::ser::Serializer for Encoder<'_> {
         _name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
-        Ok(self)
+        Ok(StructEncoder::new(self))
     }
 
     fn serialize_struct_variant(
@@ -143,17 +361,34 @@ impl serde::ser::Serializer for Encoder<'_> {
         _len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
         let this = self.serialize_u32(variant_index)?;
-        Ok(this)
+        Ok(StructEncoder::new(this))
     }
 
     fn serialize_newtype_struct<T>(
         self,
-        _name: &'static str,
+        name: &'static str,
         value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
         T: serde::ser::Serialize + ?Sized,
     {
+        if name == attachment::NAME {
+            return self.serialize_attachment(value);
+        }
+
+        if name == indexed::NAME {
+            return value.serialize(IndexedEncoder { this: self });
+        }
+
+        if name == lazy::NAME {
+            return self.serialize_frame(value);
+        }
+
+        #[cfg(feature = "bytemuck")]
+        if let Some(size) = aligned::element_size(name) {
+            return self.serialize_bulk(size, value);
+        }
+
         value.serialize(self)
     }
 
@@ -185,7 +420,10 @@ impl serde::ser::Serializer for Encoder<'_> {
     }
 }
 
-impl serde::ser::SerializeSeq for Encoder<'_> {
+impl<B> serde::ser::SerializeSeq for Encoder<'_, B>
+where
+    B: Buffer,
+{
     type Ok = Self;
     type Error = Box<Error>;
 
@@ -202,7 +440,10 @@ impl serde::ser::SerializeSeq for Encoder<'_> {
     }
 }
 
-impl serde::ser::SerializeTuple for Encoder<'_> {
+impl<B> serde::ser::SerializeTuple for Encoder<'_, B>
+where
+    B: Buffer,
+{
     type Ok = Self;
     type Error = Box<Error>;
 
@@ -219,7 +460,10 @@ impl serde::ser::SerializeTuple for Encoder<'_> {
     }
 }
 
-impl serde::ser::SerializeTupleStruct for Encoder<'_> {
+impl<B> serde::ser::SerializeTupleStruct for Encoder<'_, B>
+where
+    B: Buffer,
+{
     type Ok = Self;
     type Error = Box<Error>;
 
@@ -236,7 +480,10 @@ impl serde::ser::SerializeTupleStruct for Encoder<'_> {
     }
 }
 
-impl serde::ser::SerializeTupleVariant for Encoder<'_> {
+impl<B> serde::ser::SerializeTupleVariant for Encoder<'_, B>
+where
+    B: Buffer,
+{
     type Ok = Self;
     type Error = Box<Error>;
 
@@ -253,61 +500,505 @@ impl serde::ser::SerializeTupleVariant for Encoder<'_> {
     }
 }
 
-impl serde::ser::SerializeMap for Encoder<'_> {
-    type Ok = Self;
+pub struct MapEncoder<'a, B = Vec<u8>> {
+    this: Encoder<'a, B>,
+    entries: Option<Vec<Entry>>,
+}
+
+struct Entry {
+    bytes: Vec<u8>,
+    key_len: usize,
+}
+
+impl<B> MapEncoder<'_, B>
+where
+    B: Buffer,
+{
+    fn serialize_entry_part<T>(&mut self, value: &T, key: bool) -> Result<(), Box<Error>>
+    where
+        T: serde::ser::Serialize + ?Sized,
+    {
+        let Some(entries) = &mut self.entries else {
+            value.serialize(self.this.reborrow())?;
+            return Ok(());
+        };
+
+        if key {
+            entries.push(Entry {
+                bytes: Vec::new(),
+                key_len: 0,
+            });
+        }
+
+        let entry = entries.last_mut().expect("Missing key");
+
+        value.serialize(Encoder {
+            buf: &mut entry.bytes,
+            start: 0,
+            attachments: self.this.attachments.as_deref_mut(),
+            config: self.this.config,
+        })?;
+
+        if key {
+            entry.key_len = entry.bytes.len();
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, B> serde::ser::SerializeMap for MapEncoder<'a, B>
+where
+    B: Buffer,
+{
+    type Ok = Encoder<'a, B>;
     type Error = Box<Error>;
 
     fn serialize_key<T>(&mut self, value: &T) -> Result<(), Self::Error>
     where
         T: serde::ser::Serialize + ?Sized,
     {
-        value.serialize(self.reborrow())?;
-        Ok(())
+        self.serialize_entry_part(value, true)
     }
 
     fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
     where
         T: serde::ser::Serialize + ?Sized,
     {
-        value.serialize(self.reborrow())?;
-        Ok(())
+        self.serialize_entry_part(value, false)
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        Ok(self)
+        #[allow(unused_mut)]
+        let mut this = self.this;
+
+        if let Some(mut entries) = self.entries {
+            entries.sort_by(|lhs, rhs| lhs.bytes[..lhs.key_len].cmp(&rhs.bytes[..rhs.key_len]));
+
+            for entry in entries {
+                #[cfg(feature = "bytemuck")]
+                if this.config.aligned {
+                    this.serialize_pad(aligned::MAX_ALIGN, 0);
+                }
+
+                this.buf.extend_from_slice(&entry.bytes);
+            }
+        }
+
+        Ok(this)
     }
 }
 
-impl serde::ser::SerializeStruct for Encoder<'_> {
-    type Ok = Self;
+pub struct StructEncoder<'a, B = Vec<u8>> {
+    this: Encoder<'a, B>,
+    presence: Option<Presence>,
+}
+
+struct Presence {
+    start: usize,
+    bitmap: Vec<u8>,
+    fields: usize,
+}
+
+impl<'a, B> StructEncoder<'a, B>
+where
+    B: Buffer,
+{
+    fn new(this: Encoder<'a, B>) -> Self {
+        let presence = this.config.presence_bitmap.then(|| Presence {
+            start: this.buf.len(),
+            bitmap: Vec::new(),
+            fields: 0,
+        });
+
+        Self { this, presence }
+    }
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Box<Error>>
+    where
+        T: serde::ser::Serialize + ?Sized,
+    {
+        let start = self.begin_field();
+
+        match &mut self.presence {
+            Some(presence) => {
+                let mut present = true;
+
+                value.serialize(FieldEncoder {
+                    this: self.this.reborrow(),
+                    present: &mut present,
+                })?;
+
+                presence.push(present);
+            }
+            None => {
+                value.serialize(self.this.reborrow())?;
+            }
+        }
+
+        self.end_field(start);
+
+        Ok(())
+    }
+
+    fn skip_field(&mut self) {
+        if let Some(presence) = &mut self.presence {
+            presence.push(false);
+
+            let start = self.begin_field();
+            self.end_field(start);
+        }
+    }
+
+    fn begin_field(&mut self) -> Option<usize> {
+        if !self.this.config.field_lengths {
+            return None;
+        }
+
+        let start = self.this.buf.len();
+        self.this.buf.extend_from_slice(&0_u32.to_ne_bytes());
+
+        Some(start)
+    }
+
+    fn end_field(&mut self, start: Option<usize>) {
+        if let Some(start) = start {
+            let len = self.this.buf.len() - start - size_of::<u32>();
+            let len: u32 = len.try_into().expect("Excessive length");
+            self.this.buf.overwrite(start, &len.to_ne_bytes());
+        }
+    }
+
+    fn end(self) -> Encoder<'a, B> {
+        if let Some(presence) = self.presence {
+            self.this.buf.insert(presence.start, &presence.bitmap);
+        }
+
+        self.this
+    }
+}
+
+impl Presence {
+    fn push(&mut self, present: bool) {
+        let (byte, bit) = (self.fields / 8, self.fields % 8);
+
+        if bit == 0 {
+            self.bitmap.push(0);
+        }
+
+        self.bitmap[byte] |= (present as u8) << bit;
+        self.fields += 1;
+    }
+}
+
+impl<'a, B> serde::ser::SerializeStruct for StructEncoder<'a, B>
+where
+    B: Buffer,
+{
+    type Ok = Encoder<'a, B>;
     type Error = Box<Error>;
 
     fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<(), Self::Error>
     where
         T: serde::ser::Serialize + ?Sized,
     {
-        value.serialize(self.reborrow())?;
+        self.serialize_field(value)
+    }
+
+    fn skip_field(&mut self, _key: &'static str) -> Result<(), Self::Error> {
+        self.skip_field();
         Ok(())
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        Ok(self)
+        Ok(self.end())
     }
 }
 
-impl serde::ser::SerializeStructVariant for Encoder<'_> {
-    type Ok = Self;
+impl<'a, B> serde::ser::SerializeStructVariant for StructEncoder<'a, B>
+where
+    B: Buffer,
+{
+    type Ok = Encoder<'a, B>;
     type Error = Box<Error>;
 
     fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<(), Self::Error>
     where
         T: serde::ser::Serialize + ?Sized,
     {
-        value.serialize(self.reborrow())?;
+        self.serialize_field(value)
+    }
+
+    fn skip_field(&mut self, _key: &'static str) -> Result<(), Self::Error> {
+        self.skip_field();
         Ok(())
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        Ok(self)
+        Ok(self.end())
+    }
+}
+
+struct FieldEncoder<'a, 'b, B> {
+    this: Encoder<'a, B>,
+    present: &'b mut bool,
+}
+
+macro_rules! forward_serialize {
+    ($method:ident($($arg:ident: $ty:ty),*) -> $ret:ident) => {
+        fn $method(self, $($arg: $ty),*) -> Result<Self::$ret, Self::Error> {
+            self.this.$method($($arg),*)
+        }
+    };
+}
+
+impl<'a, B> serde::ser::Serializer for FieldEncoder<'a, '_, B>
+where
+    B: Buffer,
+{
+    type Ok = Encoder<'a, B>;
+    type Error = Box<Error>;
+
+    type SerializeSeq = Encoder<'a, B>;
+    type SerializeTuple = Encoder<'a, B>;
+    type SerializeTupleStruct = Encoder<'a, B>;
+    type SerializeTupleVariant = Encoder<'a, B>;
+    type SerializeMap = MapEncoder<'a, B>;
+    type SerializeStruct = StructEncoder<'a, B>;
+    type SerializeStructVariant = StructEncoder<'a, B>;
+
+    forward_serialize!(serialize_i8(value: i8) -> Ok);
+    forward_serialize!(serialize_i16(value: i16) -> Ok);
+    forward_serialize!(serialize_i32(value: i32) -> Ok);
+    forward_serialize!(serialize_i64(value: i64) -> Ok);
+    forward_serialize!(serialize_i128(value: i128) -> Ok);
+
+    forward_serialize!(serialize_u8(value: u8) -> Ok);
+    forward_serialize!(serialize_u16(value: u16) -> Ok);
+    forward_serialize!(serialize_u32(value: u32) -> Ok);
+    forward_serialize!(serialize_u64(value: u64) -> Ok);
+    forward_serialize!(serialize_u128(value: u128) -> Ok);
+
+    forward_serialize!(serialize_f32(value: f32) -> Ok);
+    forward_serialize!(serialize_f64(value: f64) -> Ok);
+
+    forward_serialize!(serialize_bool(value: bool) -> Ok);
+    forward_serialize!(serialize_char(value: char) -> Ok);
+    forward_serialize!(serialize_bytes(value: &[u8]) -> Ok);
+    forward_serialize!(serialize_str(value: &str) -> Ok);
+
+    forward_serialize!(serialize_unit() -> Ok);
+    forward_serialize!(serialize_unit_struct(name: &'static str) -> Ok);
+    forward_serialize!(serialize_unit_variant(name: &'static str, variant_index: u32, variant: &'static str) -> Ok);
+
+    forward_serialize!(serialize_seq(len: Option<usize>) -> SerializeSeq);
+    forward_serialize!(serialize_tuple(len: usize) -> SerializeTuple);
+    forward_serialize!(serialize_tuple_struct(name: &'static str, len: usize) -> SerializeTupleStruct);
+    forward_serialize!(serialize_tuple_variant(name: &'static str, variant_index: u32, variant: &'static str, len: usize) -> SerializeTupleVariant);
+    forward_serialize!(serialize_map(len: Option<usize>) -> SerializeMap);
+    forward_serialize!(serialize_struct(name: &'static str, len: usize) -> SerializeStruct);
+    forward_serialize!(serialize_struct_variant(name: &'static str, variant_index: u32, variant: &'static str, len: usize) -> SerializeStructVariant);
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        *self.present = false;
+        Ok(self.this)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        value.serialize(self.this)
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::ser::Serialize + ?Sized,
+    {
+        self.this.serialize_newtype_struct(name, value)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::ser::Serialize + ?Sized,
+    {
+        self.this
+            .serialize_newtype_variant(name, variant_index, variant, value)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+struct IndexedEncoder<'a, B> {
+    this: Encoder<'a, B>,
+}
+
+struct IndexedSeqEncoder<'a, B> {
+    this: Encoder<'a, B>,
+    table: usize,
+    data: usize,
+    len: usize,
+    index: usize,
+}
+
+macro_rules! unsupported {
+    ($method:ident($($arg:ident: $ty:ty),*) -> $ret:ident) => {
+        fn $method(self, $(_: $ty),*) -> Result<Self::$ret, Self::Error> {
+            Error::NotSupported.into()
+        }
+    };
+}
+
+impl<'a, B> serde::ser::Serializer for IndexedEncoder<'a, B>
+where
+    B: Buffer,
+{
+    type Ok = Encoder<'a, B>;
+    type Error = Box<Error>;
+
+    type SerializeSeq = IndexedSeqEncoder<'a, B>;
+    type SerializeTuple = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleVariant = Impossible<Self::Ok, Self::Error>;
+    type SerializeMap = Impossible<Self::Ok, Self::Error>;
+    type SerializeStruct = Impossible<Self::Ok, Self::Error>;
+    type SerializeStructVariant = Impossible<Self::Ok, Self::Error>;
+
+    unsupported!(serialize_i8(value: i8) -> Ok);
+    unsupported!(serialize_i16(value: i16) -> Ok);
+    unsupported!(serialize_i32(value: i32) -> Ok);
+    unsupported!(serialize_i64(value: i64) -> Ok);
+    unsupported!(serialize_i128(value: i128) -> Ok);
+
+    unsupported!(serialize_u8(value: u8) -> Ok);
+    unsupported!(serialize_u16(value: u16) -> Ok);
+    unsupported!(serialize_u32(value: u32) -> Ok);
+    unsupported!(serialize_u64(value: u64) -> Ok);
+    unsupported!(serialize_u128(value: u128) -> Ok);
+
+    unsupported!(serialize_f32(value: f32) -> Ok);
+    unsupported!(serialize_f64(value: f64) -> Ok);
+
+    unsupported!(serialize_bool(value: bool) -> Ok);
+    unsupported!(serialize_char(value: char) -> Ok);
+    unsupported!(serialize_bytes(value: &[u8]) -> Ok);
+    unsupported!(serialize_str(value: &str) -> Ok);
+
+    unsupported!(serialize_unit() -> Ok);
+    unsupported!(serialize_none() -> Ok);
+    unsupported!(serialize_unit_struct(name: &'static str) -> Ok);
+    unsupported!(serialize_unit_variant(name: &'static str, variant_index: u32, variant: &'static str) -> Ok);
+
+    unsupported!(serialize_tuple(len: usize) -> SerializeTuple);
+    unsupported!(serialize_tuple_struct(name: &'static str, len: usize) -> SerializeTupleStruct);
+    unsupported!(serialize_tuple_variant(name: &'static str, variant_index: u32, variant: &'static str, len: usize) -> SerializeTupleVariant);
+    unsupported!(serialize_map(len: Option<usize>) -> SerializeMap);
+    unsupported!(serialize_struct(name: &'static str, len: usize) -> SerializeStruct);
+    unsupported!(serialize_struct_variant(name: &'static str, variant_index: u32, variant: &'static str, len: usize) -> SerializeStructVariant);
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        let len = len.expect("Missing length");
+        let this = self.this.serialize_len(len)?;
+
+        let table = this.buf.len();
+
+        for _ in 0..len {
+            this.buf.extend_from_slice(&0_u32.to_ne_bytes());
+        }
+
+        let data = this.buf.len();
+
+        Ok(IndexedSeqEncoder {
+            this,
+            table,
+            data,
+            len,
+            index: 0,
+        })
+    }
+
+    fn serialize_some<T>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        Error::NotSupported.into()
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::ser::Serialize + ?Sized,
+    {
+        Error::NotSupported.into()
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: serde::ser::Serialize + ?Sized,
+    {
+        Error::NotSupported.into()
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+impl<'a, B> serde::ser::SerializeSeq for IndexedSeqEncoder<'a, B>
+where
+    B: Buffer,
+{
+    type Ok = Encoder<'a, B>;
+    type Error = Box<Error>;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: serde::ser::Serialize + ?Sized,
+    {
+        if self.index == self.len {
+            return Error::InvalidLength.into();
+        }
+
+        value.serialize(self.this.reborrow())?;
+
+        let end = self.this.buf.len() - self.data;
+        let end: u32 = end.try_into().expect("Excessive length");
+        self.this.buf.overwrite(
+            self.table + self.index * size_of::<u32>(),
+            &end.to_ne_bytes(),
+        );
+
+        self.index += 1;
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        if self.index != self.len {
+            return Error::InvalidLength.into();
+        }
+
+        Ok(self.this)
     }
 }
@@ -0,0 +1,75 @@
+use std::cell::RefCell;
+
+use super::{Config, Error};
+
+#[derive(Debug, Default)]
+pub struct Serializer {
+    buf: Vec<u8>,
+    config: Config,
+}
+
+impl Serializer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_config(config: Config) -> Self {
+        Self {
+            buf: Vec::new(),
+            config,
+        }
+    }
+
+    /// Run `f` using a serializer cached per thread, falling back to a fresh one if that is already in use.
+    pub fn with_thread_local<F, R>(f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        thread_local! {
+            static SERIALIZER: RefCell<Serializer> = RefCell::default();
+        }
+
+        SERIALIZER.with(|serializer| match serializer.try_borrow_mut() {
+            Ok(mut serializer) => f(&mut serializer),
+            Err(_) => f(&mut Self::new()),
+        })
+    }
+
+    pub fn serialize<T>(&mut self, value: &T) -> Result<&[u8], Box<Error>>
+    where
+        T: serde::ser::Serialize,
+    {
+        self.serialize_with(self.config, value)
+    }
+
+    pub(crate) fn serialize_with<T>(
+        &mut self,
+        config: Config,
+        value: &T,
+    ) -> Result<&[u8], Box<Error>>
+    where
+        T: serde::ser::Serialize,
+    {
+        self.buf.clear();
+
+        if let Err(err) = config.serialize_into(&mut self.buf, value) {
+            self.buf.clear();
+
+            return Err(err);
+        }
+
+        Ok(&self.buf)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf
+    }
+
+    pub fn clear(&mut self) {
+        self.buf.clear();
+    }
+
+    pub fn shrink_to(&mut self, capacity: usize) {
+        self.buf.shrink_to(capacity);
+    }
+}
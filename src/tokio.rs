@@ -0,0 +1,66 @@
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+
+use super::iter::ValueReader;
+use super::{Config, Error};
+
+pub async fn serialize_to_async_writer<W, T>(writer: &mut W, value: &T) -> Result<(), Box<Error>>
+where
+    W: AsyncWrite + Unpin,
+    T: serde::ser::Serialize,
+{
+    serialize_to_async_writer_with_config(writer, value, Config::default()).await
+}
+
+pub async fn serialize_to_async_writer_with_config<W, T>(
+    writer: &mut W,
+    value: &T,
+    config: Config,
+) -> Result<(), Box<Error>>
+where
+    W: AsyncWrite + Unpin,
+    T: serde::ser::Serialize,
+{
+    let buf = config.serialize(value)?;
+
+    writer.write_all(&buf).await?;
+
+    Ok(())
+}
+
+pub async fn deserialize_from_async_reader<R, T>(
+    reader: &mut R,
+    max_len: usize,
+) -> Result<T, Box<Error>>
+where
+    R: AsyncBufRead + Unpin,
+    T: serde::de::DeserializeOwned,
+{
+    deserialize_from_async_reader_with_config(reader, max_len, Config::default()).await
+}
+
+/// Read exactly one value of at most `max_len` bytes, retrying to decode it once the reader provided the bytes known to be missing.
+///
+/// As nothing beyond the value may be consumed, a value much larger than the buffer of the reader can still be decoded
+/// repeatedly, e.g. a long sequence of numbers, so consider framing such values and reading them using [`MincodeCodec`](crate::MincodeCodec).
+pub async fn deserialize_from_async_reader_with_config<R, T>(
+    reader: &mut R,
+    max_len: usize,
+    config: Config,
+) -> Result<T, Box<Error>>
+where
+    R: AsyncBufRead + Unpin,
+    T: serde::de::DeserializeOwned,
+{
+    let mut value_reader = ValueReader::new(max_len, config);
+
+    loop {
+        let available = reader.fill_buf().await?;
+
+        let (value, used) = value_reader.step(available)?;
+        reader.consume(used);
+
+        if let Some(value) = value {
+            return Ok(value);
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::io::IoSlice;
+
+use bytes::Bytes;
+
+use super::bytes::take_shared;
+use super::ser::Buffer;
+
+/// Output which references large `Bytes` fields instead of copying them
+///
+/// Only fields serialized via [`serde_mincode::bytes`](crate::bytes) which are
+/// at least `threshold` bytes long are referenced.
+pub struct Vectored {
+    inline: Vec<u8>,
+    shared: Vec<(usize, Bytes)>,
+    shared_len: usize,
+    threshold: usize,
+}
+
+impl Vectored {
+    pub fn new(threshold: usize) -> Self {
+        Self {
+            inline: Vec::new(),
+            shared: Vec::new(),
+            shared_len: 0,
+            threshold,
+        }
+    }
+
+    pub fn io_slices(&self) -> Vec<IoSlice<'_>> {
+        let mut slices = Vec::with_capacity(2 * self.shared.len() + 1);
+        let mut start = 0;
+
+        for (pos, bytes) in &self.shared {
+            if start != *pos {
+                slices.push(IoSlice::new(&self.inline[start..*pos]));
+            }
+
+            slices.push(IoSlice::new(bytes));
+            start = *pos;
+        }
+
+        if start != self.inline.len() {
+            slices.push(IoSlice::new(&self.inline[start..]));
+        }
+
+        slices
+    }
+
+    pub fn clear(&mut self) {
+        self.inline.clear();
+        self.shared.clear();
+        self.shared_len = 0;
+    }
+
+    fn inline_pos(&self, pos: usize) -> usize {
+        let mut shared_len = 0;
+
+        for (inline_pos, bytes) in &self.shared {
+            if inline_pos + shared_len >= pos {
+                break;
+            }
+
+            shared_len += bytes.len();
+        }
+
+        pos - shared_len
+    }
+}
+
+impl Buffer for Vectored {
+    fn len(&self) -> usize {
+        self.inline.len() + self.shared_len
+    }
+
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        if bytes.len() >= self.threshold {
+            if let Some(bytes) = take_shared(bytes) {
+                self.shared_len += bytes.len();
+                self.shared.push((self.inline.len(), bytes));
+
+                return;
+            }
+        }
+
+        self.inline.extend_from_slice(bytes);
+    }
+
+    fn insert(&mut self, pos: usize, bytes: &[u8]) {
+        let pos = self.inline_pos(pos);
+
+        Buffer::insert(&mut self.inline, pos, bytes);
+
+        for (inline_pos, _) in &mut self.shared {
+            if *inline_pos > pos {
+                *inline_pos += bytes.len();
+            }
+        }
+    }
+
+    fn overwrite(&mut self, pos: usize, bytes: &[u8]) {
+        let pos = self.inline_pos(pos);
+
+        Buffer::overwrite(&mut self.inline, pos, bytes);
+    }
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "bytemuck")]
+
+use serde::{Deserialize, Serialize};
+use serde_mincode::{AlignedSlice, Config};
+
+fn aligned(buf: &[u8]) -> Vec<u128> {
+    let mut aligned = vec![0_u128; buf.len().div_ceil(16)];
+    bytemuck::cast_slice_mut::<_, u8>(&mut aligned)[..buf.len()].copy_from_slice(buf);
+    aligned
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Tensor<'a> {
+    name: String,
+    #[serde(borrow)]
+    data: AlignedSlice<'a, f64>,
+}
+
+#[test]
+fn aligned_slices_are_borrowed() {
+    let config = Config::default().aligned(true);
+
+    for len in 0..16 {
+        let value = Tensor {
+            name: "x".repeat(len),
+            data: vec![1.0, 2.0, 3.0].into(),
+        };
+
+        let buf = config.serialize(&value).unwrap();
+        let storage = aligned(&buf);
+        let buf = &bytemuck::cast_slice::<_, u8>(&storage)[..buf.len()];
+
+        let decoded = config.deserialize::<Tensor>(buf).unwrap();
+        assert!(decoded.data.is_borrowed());
+        assert_eq!(decoded, value);
+    }
+}
+
+#[test]
+fn aligned_slices_are_copied_if_misaligned() {
+    let value = Tensor {
+        name: "x".to_owned(),
+        data: vec![1.0, 2.0, 3.0].into(),
+    };
+
+    for config in [Config::default(), Config::default().aligned(true)] {
+        let buf = config.serialize(&value).unwrap();
+        let storage = aligned(&[&[0][..], &buf].concat());
+        let buf = &bytemuck::cast_slice::<_, u8>(&storage)[1..=buf.len()];
+
+        let decoded = config.deserialize::<Tensor>(buf).unwrap();
+        assert!(!decoded.data.is_borrowed());
+        assert_eq!(decoded, value);
+    }
+}
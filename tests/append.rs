@@ -0,0 +1,106 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_mincode::Config;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Record {
+    id: u64,
+    name: String,
+    note: Option<String>,
+    score: Option<f64>,
+    tags: Vec<String>,
+    attrs: BTreeMap<String, i32>,
+    kind: Kind,
+    inner: Inner,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+struct Inner {
+    flag: bool,
+    values: Vec<u16>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Kind {
+    Unit,
+    Newtype(u8),
+    Struct { x: Option<i8>, y: char },
+}
+
+fn record() -> Record {
+    Record {
+        id: 42,
+        name: "answer".to_owned(),
+        note: None,
+        score: Some(0.5),
+        tags: vec!["a".to_owned(), "b".to_owned()],
+        attrs: [("x".to_owned(), 1), ("y".to_owned(), -1)].into(),
+        kind: Kind::Struct { x: None, y: 'z' },
+        inner: Inner {
+            flag: true,
+            values: Vec::new(),
+        },
+    }
+}
+
+fn configs() -> Vec<Config> {
+    let mut configs = Vec::new();
+
+    for bits in 0..8 {
+        configs.push(
+            Config::default()
+                .presence_bitmap(bits & 1 != 0)
+                .field_lengths(bits & 2 != 0)
+                .canonical(bits & 4 != 0),
+        );
+    }
+
+    configs
+}
+
+#[test]
+fn append_to_sequences() {
+    for config in configs() {
+        let mut buf = Vec::new();
+        let mut values = Vec::new();
+
+        for id in 0..3 {
+            let mut value = record();
+            value.id = id;
+
+            config.append(&mut buf, &value).unwrap();
+            values.push(value);
+
+            assert_eq!(config.deserialize::<Vec<Record>>(&buf).unwrap(), values);
+        }
+    }
+}
+
+#[test]
+fn append_to_encoded_vec() {
+    let mut buf = serde_mincode::serialize(&vec![1_u32, 2]).unwrap();
+    serde_mincode::append(&mut buf, &3_u32).unwrap();
+
+    assert_eq!(
+        serde_mincode::deserialize::<Vec<u32>>(&buf).unwrap(),
+        [1, 2, 3]
+    );
+
+    let mut buf = vec![1, 0];
+    assert!(serde_mincode::append(&mut buf, &3_u32)
+        .unwrap_err()
+        .is_incomplete());
+    assert_eq!(buf, [1, 0]);
+}
+
+#[test]
+fn append_rejects_excessive_lengths() {
+    let mut buf = u32::MAX.to_ne_bytes().to_vec();
+
+    assert!(matches!(
+        *serde_mincode::append(&mut buf, &3_u32).unwrap_err(),
+        serde_mincode::Error::InvalidLength
+    ));
+    assert_eq!(buf, u32::MAX.to_ne_bytes());
+}
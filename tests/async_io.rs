@@ -0,0 +1,322 @@
+#![cfg(any(feature = "tokio", feature = "futures-io"))]
+
+use std::cell::Cell;
+use std::future::Future;
+use std::io;
+use std::pin::{pin, Pin};
+use std::task::{Context, Poll, Waker};
+
+use serde::Deserialize;
+use serde_mincode::Config;
+
+fn block_on<F>(fut: F) -> F::Output
+where
+    F: Future,
+{
+    let mut fut = pin!(fut);
+    let mut cx = Context::from_waker(Waker::noop());
+
+    loop {
+        if let Poll::Ready(output) = fut.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+/// Provides at most `chunk` bytes per fill and is pending every other time.
+struct Chunked<'a> {
+    buf: &'a [u8],
+    chunk: usize,
+    pending: bool,
+}
+
+impl<'a> Chunked<'a> {
+    fn new(buf: &'a [u8], chunk: usize) -> Self {
+        Self {
+            buf,
+            chunk,
+            pending: false,
+        }
+    }
+
+    fn poll_fill_buf(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        self.pending = !self.pending;
+
+        if self.pending {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        let len = self.chunk.min(self.buf.len());
+        Poll::Ready(Ok(&self.buf[..len]))
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.buf = &self.buf[amt..];
+    }
+
+    fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let available = match self.poll_fill_buf(cx) {
+            Poll::Ready(Ok(available)) => available,
+            Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+            Poll::Pending => return Poll::Pending,
+        };
+
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.consume(len);
+
+        Poll::Ready(Ok(len))
+    }
+}
+
+thread_local! {
+    static ATTEMPTS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Counts how often decoding was attempted on the current thread.
+struct Counted(String);
+
+impl<'de> Deserialize<'de> for Counted {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        ATTEMPTS.set(ATTEMPTS.get() + 1);
+
+        String::deserialize(deserializer).map(Self)
+    }
+}
+
+fn values() -> Vec<(u8, String)> {
+    (0..3)
+        .map(|index| (index, "x".repeat(10 * index as usize)))
+        .collect()
+}
+
+#[cfg(feature = "tokio")]
+mod with_tokio {
+    use tokio::io::{AsyncBufRead, AsyncRead, ReadBuf};
+
+    use serde_mincode::tokio::{
+        deserialize_from_async_reader, deserialize_from_async_reader_with_config,
+        serialize_to_async_writer_with_config,
+    };
+
+    use super::*;
+
+    impl AsyncRead for Chunked<'_> {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            let this = self.get_mut();
+
+            match this.poll_read(cx, buf.initialize_unfilled()) {
+                Poll::Ready(Ok(read)) => {
+                    buf.advance(read);
+                    Poll::Ready(Ok(()))
+                }
+                Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+                Poll::Pending => Poll::Pending,
+            }
+        }
+    }
+
+    impl AsyncBufRead for Chunked<'_> {
+        fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+            self.get_mut().poll_fill_buf(cx)
+        }
+
+        fn consume(self: Pin<&mut Self>, amt: usize) {
+            self.get_mut().consume(amt);
+        }
+    }
+
+    #[test]
+    fn read_consecutive_values() {
+        let config = Config::default().presence_bitmap(true);
+
+        let mut buf = Vec::new();
+        for value in values() {
+            block_on(serialize_to_async_writer_with_config(
+                &mut buf, &value, config,
+            ))
+            .unwrap();
+        }
+
+        for chunk in 1..=buf.len() {
+            let mut reader = Chunked::new(&buf, chunk);
+
+            for value in values() {
+                let decoded = block_on(
+                    deserialize_from_async_reader_with_config::<_, (u8, String)>(
+                        &mut reader,
+                        64,
+                        config,
+                    ),
+                )
+                .unwrap();
+                assert_eq!(decoded, value);
+            }
+
+            assert!(reader.buf.is_empty());
+        }
+    }
+
+    #[test]
+    fn reading_values_is_limited() {
+        let buf = serde_mincode::serialize(&"x".repeat(100)).unwrap();
+
+        let err = block_on(deserialize_from_async_reader::<_, String>(
+            &mut Chunked::new(&buf, 8),
+            64,
+        ))
+        .unwrap_err();
+        assert!(matches!(*err, serde_mincode::Error::FrameTooLarge));
+
+        let err = block_on(deserialize_from_async_reader::<_, String>(
+            &mut Chunked::new(&buf[..50], 8),
+            1024,
+        ))
+        .unwrap_err();
+        assert!(err.is_incomplete());
+    }
+
+    #[test]
+    fn collect_known_parts_of_values_without_decoding_them() {
+        let value = "x".repeat(1000);
+        let buf = serde_mincode::serialize(&value).unwrap();
+
+        let decoded = block_on(deserialize_from_async_reader::<_, Counted>(
+            &mut Chunked::new(&buf, 1),
+            1024,
+        ))
+        .unwrap();
+        assert_eq!(decoded.0, value);
+        assert_eq!(ATTEMPTS.get(), 3);
+    }
+}
+
+#[cfg(feature = "futures-io")]
+mod with_futures {
+    use futures_io::{AsyncBufRead, AsyncRead, AsyncWrite};
+
+    use serde_mincode::futures::{
+        deserialize_from_async_reader, deserialize_from_async_reader_with_config,
+        serialize_to_async_writer_with_config,
+    };
+
+    use super::*;
+
+    impl AsyncRead for Chunked<'_> {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            self.get_mut().poll_read(cx, buf)
+        }
+    }
+
+    impl AsyncBufRead for Chunked<'_> {
+        fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+            self.get_mut().poll_fill_buf(cx)
+        }
+
+        fn consume(self: Pin<&mut Self>, amt: usize) {
+            self.get_mut().consume(amt);
+        }
+    }
+
+    /// Accepts at most three bytes per write.
+    struct Trickle(Vec<u8>);
+
+    impl AsyncWrite for Trickle {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            let len = buf.len().min(3);
+            self.get_mut().0.extend_from_slice(&buf[..len]);
+            Poll::Ready(Ok(len))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[test]
+    fn read_consecutive_values() {
+        let config = Config::default().presence_bitmap(true);
+
+        let mut writer = Trickle(Vec::new());
+        for value in values() {
+            block_on(serialize_to_async_writer_with_config(
+                &mut writer,
+                &value,
+                config,
+            ))
+            .unwrap();
+        }
+        let buf = writer.0;
+
+        for chunk in 1..=buf.len() {
+            let mut reader = Chunked::new(&buf, chunk);
+
+            for value in values() {
+                let decoded = block_on(
+                    deserialize_from_async_reader_with_config::<_, (u8, String)>(
+                        &mut reader,
+                        64,
+                        config,
+                    ),
+                )
+                .unwrap();
+                assert_eq!(decoded, value);
+            }
+
+            assert!(reader.buf.is_empty());
+        }
+    }
+
+    #[test]
+    fn reading_values_is_limited() {
+        let buf = serde_mincode::serialize(&"x".repeat(100)).unwrap();
+
+        let err = block_on(deserialize_from_async_reader::<_, String>(
+            &mut Chunked::new(&buf, 8),
+            64,
+        ))
+        .unwrap_err();
+        assert!(matches!(*err, serde_mincode::Error::FrameTooLarge));
+
+        let err = block_on(deserialize_from_async_reader::<_, String>(
+            &mut Chunked::new(&buf[..50], 8),
+            1024,
+        ))
+        .unwrap_err();
+        assert!(err.is_incomplete());
+    }
+
+    #[test]
+    fn collect_known_parts_of_values_without_decoding_them() {
+        let value = "x".repeat(1000);
+        let buf = serde_mincode::serialize(&value).unwrap();
+
+        let decoded = block_on(deserialize_from_async_reader::<_, Counted>(
+            &mut Chunked::new(&buf, 1),
+            1024,
+        ))
+        .unwrap();
+        assert_eq!(decoded.0, value);
+        assert_eq!(ATTEMPTS.get(), 3);
+    }
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use serde_mincode::Error;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Upload<'a> {
+    name: String,
+    #[serde(with = "serde_mincode::attachment")]
+    data: Vec<u8>,
+    #[serde(borrow, with = "serde_mincode::attachment")]
+    text: &'a str,
+}
+
+fn upload() -> Upload<'static> {
+    Upload {
+        name: "upload".to_owned(),
+        data: vec![0xab; 100],
+        text: "text",
+    }
+}
+
+#[test]
+fn attachments_are_encoded_out_of_band() {
+    let (buf, attachments) = serde_mincode::serialize_with_attachments(&upload()).unwrap();
+    assert_eq!(attachments, [vec![0xab; 100], b"text".to_vec()]);
+    assert!(buf.len() < 100);
+
+    let attachments = attachments.iter().map(Vec::as_slice).collect::<Vec<_>>();
+    let decoded =
+        serde_mincode::deserialize_with_attachments::<Upload>(&buf, &attachments).unwrap();
+    assert_eq!(decoded, upload());
+
+    assert!(matches!(
+        *serde_mincode::deserialize_with_attachments::<Upload>(&buf, &attachments[..1])
+            .unwrap_err(),
+        Error::InvalidAttachment
+    ));
+}
+
+#[test]
+fn attachments_are_inline_otherwise() {
+    let buf = serde_mincode::serialize(&upload()).unwrap();
+
+    let decoded = serde_mincode::deserialize::<Upload>(&buf).unwrap();
+    assert_eq!(decoded, upload());
+}
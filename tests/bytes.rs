@@ -0,0 +1,45 @@
+#![cfg(feature = "bytes")]
+
+use bytes::{Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Blob {
+    id: u32,
+    #[serde(with = "serde_mincode::bytes")]
+    data: Bytes,
+}
+
+fn blob() -> Blob {
+    Blob {
+        id: 1,
+        data: Bytes::from(vec![0xab; 64]),
+    }
+}
+
+#[test]
+fn bytes_fields_reference_the_input() {
+    let buf = Bytes::from(serde_mincode::serialize(&blob()).unwrap());
+
+    let decoded = serde_mincode::deserialize_from_bytes::<Blob>(&buf).unwrap();
+    assert_eq!(decoded, blob());
+
+    let range = buf.as_ptr_range();
+    assert!(range.contains(&decoded.data.as_ptr()));
+
+    let decoded = serde_mincode::deserialize::<Blob>(&buf).unwrap();
+    assert_eq!(decoded, blob());
+    assert!(!range.contains(&decoded.data.as_ptr()));
+}
+
+#[test]
+fn serialize_into_byte_buffers() {
+    let mut buf = BytesMut::from(&b"head"[..]);
+    serde_mincode::serialize_into_buf(&mut buf, &blob()).unwrap();
+
+    assert_eq!(&buf[..4], b"head");
+    assert_eq!(
+        serde_mincode::deserialize::<Blob>(&buf[4..]).unwrap(),
+        blob()
+    );
+}
@@ -0,0 +1,234 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+use serde_mincode::{Config, Error};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Record {
+    id: u64,
+    name: String,
+    note: Option<String>,
+    score: Option<f64>,
+    tags: Vec<String>,
+    attrs: BTreeMap<String, i32>,
+    kind: Kind,
+    inner: Inner,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+struct Inner {
+    flag: bool,
+    values: Vec<u16>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Kind {
+    Unit,
+    Newtype(u8),
+    Struct { x: Option<i8>, y: char },
+}
+
+fn record() -> Record {
+    Record {
+        id: 42,
+        name: "answer".to_owned(),
+        note: None,
+        score: Some(0.5),
+        tags: vec!["a".to_owned(), "b".to_owned()],
+        attrs: [("x".to_owned(), 1), ("y".to_owned(), -1)].into(),
+        kind: Kind::Struct { x: None, y: 'z' },
+        inner: Inner {
+            flag: true,
+            values: Vec::new(),
+        },
+    }
+}
+
+fn configs() -> Vec<Config> {
+    let mut configs = Vec::new();
+
+    for bits in 0..8 {
+        configs.push(
+            Config::default()
+                .presence_bitmap(bits & 1 != 0)
+                .field_lengths(bits & 2 != 0)
+                .canonical(bits & 4 != 0),
+        );
+    }
+
+    configs
+}
+
+#[test]
+fn round_trip_with_all_configs() {
+    let mut value = record();
+
+    for config in configs() {
+        for kind in [Kind::Unit, Kind::Newtype(7), value.kind.clone()] {
+            value.kind = kind;
+            value.note = value.note.take().xor(Some("note".to_owned()));
+
+            let buf = config.serialize(&value).unwrap();
+            assert_eq!(config.deserialize::<Record>(&buf).unwrap(), value);
+        }
+    }
+}
+
+#[test]
+fn canonical_encoding_is_deterministic() {
+    let config = Config::default().canonical(true);
+
+    let map: HashMap<String, f32> = (0..64)
+        .map(|key| (key.to_string(), f32::from_bits(0x7fc0_0000 | key)))
+        .collect();
+    let sorted: BTreeMap<String, f32> = map.iter().map(|(k, v)| (k.clone(), *v)).collect();
+
+    let buf = config.serialize(&map).unwrap();
+    assert_eq!(buf, config.serialize(&sorted).unwrap());
+
+    let decoded = config.deserialize::<BTreeMap<String, f32>>(&buf).unwrap();
+    assert!(decoded
+        .values()
+        .all(|value| value.to_bits() == f32::NAN.to_bits()));
+}
+
+#[test]
+fn canonical_decoding_rejects_other_encodings() {
+    let config = Config::default().canonical(true);
+
+    let unsorted = serde_mincode::serialize(&vec![(2_u8, ()), (1_u8, ())]).unwrap();
+    let duplicate = serde_mincode::serialize(&vec![(1_u8, ()), (1_u8, ())]).unwrap();
+    let sorted = serde_mincode::serialize(&vec![(1_u8, ()), (2_u8, ())]).unwrap();
+
+    for buf in [&unsorted, &duplicate] {
+        assert!(matches!(
+            *config.deserialize::<BTreeMap<u8, ()>>(buf).unwrap_err(),
+            Error::NonCanonical
+        ));
+        serde_mincode::deserialize::<BTreeMap<u8, ()>>(buf).unwrap();
+    }
+    config.deserialize::<BTreeMap<u8, ()>>(&sorted).unwrap();
+
+    let nan = serde_mincode::serialize(&f64::from_bits(0x7ff8_0000_0000_0001)).unwrap();
+    assert!(matches!(
+        *config.deserialize::<f64>(&nan).unwrap_err(),
+        Error::NonCanonical
+    ));
+
+    let config = config.presence_bitmap(true);
+    let mut buf = config.serialize(&record().inner).unwrap();
+    buf[0] |= 0b100;
+    assert!(matches!(
+        *config.deserialize::<Inner>(&buf).unwrap_err(),
+        Error::NonCanonical
+    ));
+}
+
+#[test]
+fn canonical_decoding_rejects_padded_fields() {
+    let config = Config::default().field_lengths(true);
+    let value = record().inner;
+
+    let mut buf = config.serialize(&value).unwrap();
+    assert_eq!(buf[..4], 1_u32.to_ne_bytes());
+    buf[..4].copy_from_slice(&5_u32.to_ne_bytes());
+    buf.splice(5..5, [0xff; 4]);
+
+    assert_eq!(config.deserialize::<Inner>(&buf).unwrap(), value);
+    assert!(matches!(
+        *config
+            .canonical(true)
+            .deserialize::<Inner>(&buf)
+            .unwrap_err(),
+        Error::NonCanonical
+    ));
+}
+
+#[test]
+fn canonical_decoding_rejects_trailing_data() {
+    let config = Config::default().canonical(true);
+
+    let mut buf = config.serialize(&1_u32).unwrap();
+    buf.push(0xff);
+
+    assert_eq!(Config::default().deserialize::<u32>(&buf).unwrap(), 1);
+    assert!(matches!(
+        *config.deserialize::<u32>(&buf).unwrap_err(),
+        Error::TrailingData
+    ));
+}
+
+#[cfg(feature = "bytemuck")]
+mod aligned {
+    use std::collections::HashMap;
+
+    use serde_mincode::{AlignedSlice, Config, Error, Pod};
+
+    fn aligned(buf: &[u8]) -> Vec<u128> {
+        let mut aligned = vec![0_u128; buf.len().div_ceil(16)];
+        bytemuck::cast_slice_mut::<_, u8>(&mut aligned)[..buf.len()].copy_from_slice(buf);
+        aligned
+    }
+
+    #[test]
+    fn aligned_slices_in_canonical_maps_are_borrowed() {
+        let config = Config::default().aligned(true).canonical(true);
+
+        let map: HashMap<u8, AlignedSlice<u64>> = (0..8)
+            .map(|key| (key, vec![key as u64; key as usize].into()))
+            .collect();
+
+        let buf = config.serialize(&map).unwrap();
+        let storage = aligned(&buf);
+        let buf = &bytemuck::cast_slice::<_, u8>(&storage)[..buf.len()];
+
+        let decoded = config
+            .deserialize::<HashMap<u8, AlignedSlice<u64>>>(buf)
+            .unwrap();
+        assert!(decoded.values().all(AlignedSlice::is_borrowed));
+        assert_eq!(decoded, map);
+    }
+
+    #[test]
+    fn canonical_decoding_rejects_bulk_nans_and_padding() {
+        let config = Config::default().canonical(true);
+
+        let buf = serde_mincode::serialize(&Pod(vec![f32::from_bits(0x7fc0_0001)])).unwrap();
+        assert!(matches!(
+            *config.deserialize::<Pod<Vec<f32>>>(&buf).unwrap_err(),
+            Error::NonCanonical
+        ));
+        serde_mincode::deserialize::<Pod<Vec<f32>>>(&buf).unwrap();
+
+        let buf = config.serialize(&Pod(vec![f32::NAN])).unwrap();
+        config.deserialize::<Pod<Vec<f32>>>(&buf).unwrap();
+
+        let config = config.aligned(true);
+        let buf = config.serialize(&(1_u8, Pod(vec![1_u64]))).unwrap();
+        assert_eq!(buf[1], 2);
+        config.deserialize::<(u8, Pod<Vec<u64>>)>(&buf).unwrap();
+
+        let mut dirty = buf.clone();
+        dirty[2] = 1;
+        assert!(matches!(
+            *config
+                .deserialize::<(u8, Pod<Vec<u64>>)>(&dirty)
+                .unwrap_err(),
+            Error::NonCanonical
+        ));
+
+        let mut shifted = buf.clone();
+        shifted[1] = 10;
+        shifted.splice(2..2, [0; 8]);
+        assert!(matches!(
+            *config
+                .deserialize::<(u8, Pod<Vec<u64>>)>(&shifted)
+                .unwrap_err(),
+            Error::NonCanonical
+        ));
+        Config::default()
+            .aligned(true)
+            .deserialize::<(u8, Pod<Vec<u64>>)>(&shifted)
+            .unwrap();
+    }
+}
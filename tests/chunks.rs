@@ -0,0 +1,94 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_mincode::{Config, Decoder, Error};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Message<'a> {
+    id: u32,
+    #[serde(borrow)]
+    name: Cow<'a, str>,
+    values: Vec<u64>,
+    attrs: BTreeMap<String, Option<i16>>,
+}
+
+fn message() -> Message<'static> {
+    Message {
+        id: 7,
+        name: Cow::Borrowed("a name which straddles chunks"),
+        values: (0..10).collect(),
+        attrs: (0..10)
+            .map(|key| (format!("key{key}"), (key % 3 != 0).then_some(key)))
+            .collect(),
+    }
+}
+
+fn split(buf: &[u8], len: usize) -> Vec<&[u8]> {
+    buf.chunks(len).collect()
+}
+
+#[test]
+fn chunked_decoding_matches_contiguous_decoding() {
+    let configs = [
+        Config::default(),
+        Config::default().presence_bitmap(true),
+        Config::default().field_lengths(true),
+        Config::default().canonical(true),
+    ];
+
+    for config in configs {
+        let value = message();
+        let buf = config.serialize(&value).unwrap();
+
+        for len in 1..=buf.len() {
+            let chunks = split(&buf, len);
+
+            let decoded = config.deserialize_chunks::<Message>(&chunks).unwrap();
+            assert_eq!(decoded, value);
+        }
+    }
+}
+
+#[test]
+fn chunked_decoding_borrows_unless_straddling() {
+    #[derive(Deserialize)]
+    struct Pair<'a> {
+        #[serde(borrow)]
+        head: Cow<'a, str>,
+        #[serde(borrow)]
+        tail: Cow<'a, str>,
+    }
+
+    let buf = serde_mincode::serialize(&("head", "tail")).unwrap();
+    let (head, tail) = buf.split_at(buf.len() - 2);
+    let chunks = [head, tail];
+
+    let pair: Pair = serde_mincode::deserialize_chunks(&chunks).unwrap();
+    assert!(matches!(pair.head, Cow::Borrowed("head")));
+    assert!(matches!(pair.tail, Cow::Owned(ref tail) if tail == "tail"));
+}
+
+#[test]
+fn chunked_decoding_tracks_position() {
+    let mut buf = serde_mincode::serialize(&1_u32).unwrap();
+    serde_mincode::serialize_into(&mut buf, &"two").unwrap();
+
+    let chunks = split(&buf, 3);
+    let mut decoder = Decoder::from_chunks(&chunks);
+
+    assert_eq!(u32::deserialize(&mut decoder).unwrap(), 1);
+    assert_eq!(decoder.position(), 4);
+    assert_eq!(String::deserialize(&mut decoder).unwrap(), "two");
+    assert!(decoder.is_empty());
+}
+
+#[test]
+fn chunked_decoding_reports_missing_data() {
+    let buf = serde_mincode::serialize(&message()).unwrap();
+    let chunks = split(&buf[..buf.len() - 5], 4);
+
+    let err = serde_mincode::deserialize_chunks::<Message>(&chunks).unwrap_err();
+    assert!(err.is_incomplete());
+    assert!(matches!(*err, Error::MissingData(_)));
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "tokio")]
+
+use bytes::BytesMut;
+use serde_mincode::{Config, Error, MincodeCodec};
+use tokio_util::codec::{Decoder, Encoder};
+
+fn values() -> Vec<(u8, String)> {
+    (0..5)
+        .map(|index| (index, "x".repeat(index as usize)))
+        .collect()
+}
+
+#[test]
+fn codec_decodes_partial_frames() {
+    let config = Config::default().presence_bitmap(true);
+    let mut codec = MincodeCodec::<(u8, String)>::with_config(1024, config);
+
+    let mut buf = BytesMut::new();
+    for value in values() {
+        codec.encode(value, &mut buf).unwrap();
+    }
+
+    let mut src = BytesMut::new();
+    let mut decoded = Vec::new();
+
+    for byte in buf {
+        src.extend_from_slice(&[byte]);
+
+        while let Some(value) = codec.decode(&mut src).unwrap() {
+            decoded.push(value);
+        }
+    }
+
+    assert_eq!(decoded, values());
+    assert!(src.is_empty());
+}
+
+#[test]
+fn codec_checks_frames() {
+    let mut codec = MincodeCodec::<String>::new(4);
+
+    let mut buf = BytesMut::new();
+    assert!(matches!(
+        *codec.encode("too long".to_owned(), &mut buf).unwrap_err(),
+        Error::FrameTooLarge
+    ));
+    assert!(buf.is_empty());
+
+    let mut frame = Vec::new();
+    serde_mincode::serialize_frame_into(&mut frame, &"too long").unwrap();
+    let mut src = BytesMut::from(&frame[..]);
+    assert!(matches!(
+        *codec.decode(&mut src).unwrap_err(),
+        Error::FrameTooLarge
+    ));
+
+    let mut codec = MincodeCodec::<u32>::new(1024);
+    let mut src = BytesMut::from(&frame[..]);
+    assert!(matches!(
+        *codec.decode(&mut src).unwrap_err(),
+        Error::TrailingData
+    ));
+}
@@ -0,0 +1,71 @@
+use serde_mincode::{Config, Error, FrameReader};
+
+fn values() -> Vec<(u8, String)> {
+    (0..5)
+        .map(|index| (index, "x".repeat(index as usize)))
+        .collect()
+}
+
+#[test]
+fn frames_are_reassembled_from_partial_input() {
+    let config = Config::default().presence_bitmap(true);
+
+    let mut buf = Vec::new();
+    for value in values() {
+        config.serialize_frame_into(&mut buf, &value).unwrap();
+    }
+
+    let mut reader = FrameReader::with_config(1024, config);
+    let mut decoded = Vec::new();
+
+    for byte in buf {
+        reader.extend(&[byte]);
+
+        while let Some(value) = reader.next_value::<(u8, String)>().unwrap() {
+            decoded.push(value);
+        }
+    }
+
+    assert_eq!(decoded, values());
+    assert!(reader.is_empty());
+}
+
+#[test]
+fn frames_are_checked() {
+    let mut buf = Vec::new();
+    serde_mincode::serialize_frame_into(&mut buf, &"too long").unwrap();
+
+    let mut reader = FrameReader::new(4);
+    reader.extend(&buf);
+    assert!(matches!(
+        *reader.next_frame().unwrap_err(),
+        Error::FrameTooLarge
+    ));
+
+    let mut reader = FrameReader::new(1024);
+    reader.extend(&buf);
+    assert!(matches!(
+        *reader.next_value::<u32>().unwrap_err(),
+        Error::TrailingData
+    ));
+}
+
+#[test]
+fn frames_are_read_from_readers() {
+    let mut buf = Vec::new();
+    for value in values() {
+        serde_mincode::serialize_frame_into(&mut buf, &value).unwrap();
+    }
+
+    let mut reader = FrameReader::new(1024);
+    let mut input = &buf[..];
+    let mut decoded = Vec::new();
+
+    while reader.read_from(&mut input).unwrap() != 0 {
+        while let Some(value) = reader.next_value::<(u8, String)>().unwrap() {
+            decoded.push(value);
+        }
+    }
+
+    assert_eq!(decoded, values());
+}
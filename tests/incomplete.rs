@@ -0,0 +1,76 @@
+use std::io::Read;
+
+fn concatenated(values: &[(u8, String)]) -> Vec<u8> {
+    let mut buf = Vec::new();
+
+    for value in values {
+        serde_mincode::serialize_into(&mut buf, value).unwrap();
+    }
+
+    buf
+}
+
+fn values() -> Vec<(u8, String)> {
+    (0..5)
+        .map(|index| (index, "x".repeat(index as usize)))
+        .collect()
+}
+
+#[test]
+fn incomplete_input_reports_needed_bytes() {
+    let buf = serde_mincode::serialize(&(1_u32, "four")).unwrap();
+
+    for len in 0..buf.len() {
+        let err = serde_mincode::deserialize::<(u32, String)>(&buf[..len]).unwrap_err();
+        assert!(err.is_incomplete());
+
+        let needed = err.needed().unwrap();
+        assert!(needed > 0 && len + needed <= buf.len());
+    }
+
+    let err = serde_mincode::deserialize::<(u32, String)>(&buf[..6]).unwrap_err();
+    assert_eq!(err.needed(), Some(2));
+    let err = serde_mincode::deserialize::<(u32, String)>(&buf[..8]).unwrap_err();
+    assert_eq!(err.needed(), Some(4));
+
+    let err = serde_mincode::deserialize::<(u32, bool)>(&[0, 0, 0, 0, 2]).unwrap_err();
+    assert!(!err.is_incomplete());
+    assert_eq!(err.needed(), None);
+}
+
+#[test]
+fn iterate_reports_truncated_final_value() {
+    let buf = concatenated(&values());
+    let buf = &buf[..buf.len() - 1];
+
+    let mut iter = serde_mincode::deserialize_iter::<(u8, String)>(buf);
+    assert_eq!(iter.by_ref().take(4).filter(Result::is_ok).count(), 4);
+    assert!(iter.next().unwrap().unwrap_err().is_incomplete());
+    assert!(iter.next().is_none());
+
+    let mut iter = serde_mincode::deserialize_iter_from_reader::<_, (u8, String)>(buf);
+    assert_eq!(iter.by_ref().take(4).filter(Result::is_ok).count(), 4);
+    assert_eq!(iter.next().unwrap().unwrap_err().needed(), Some(1));
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn reader_does_not_trust_length_prefixes() {
+    struct Counting<'a>(&'a [u8], usize);
+
+    impl Read for Counting<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.1 = self.1.max(buf.len());
+            self.0.read(buf)
+        }
+    }
+
+    let mut reader = Counting(&[0xff, 0xff, 0xff, 0xfe], 0);
+
+    let err = serde_mincode::deserialize_iter_from_reader::<_, String>(&mut reader)
+        .next()
+        .unwrap()
+        .unwrap_err();
+    assert!(err.is_incomplete());
+    assert!(reader.1 <= 64 * 1024);
+}
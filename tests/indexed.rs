@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Serialize};
+use serde_mincode::{Config, Error, Indexed, IndexedMap, IndexedMapView, IndexedView};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Item {
+    id: u32,
+    name: Option<String>,
+}
+
+fn items() -> Vec<Item> {
+    (0..10)
+        .map(|id| Item {
+            id,
+            name: (id % 2 == 0).then(|| id.to_string()),
+        })
+        .collect()
+}
+
+fn configs() -> [Config; 3] {
+    [
+        Config::default(),
+        Config::default().presence_bitmap(true),
+        Config::default().canonical(true).field_lengths(true),
+    ]
+}
+
+#[test]
+fn indexed_sequences_decode_single_elements() {
+    for config in configs() {
+        let buf = config.serialize(&Indexed(items())).unwrap();
+
+        let view = config.deserialize::<IndexedView<Item>>(&buf).unwrap();
+        assert_eq!(view.len(), 10);
+        assert_eq!(view.get(3).unwrap().unwrap(), items()[3]);
+        assert!(view.get(10).is_none());
+
+        let Indexed(vec) = config.deserialize::<Indexed<Vec<Item>>>(&buf).unwrap();
+        assert_eq!(vec, items());
+    }
+}
+
+#[test]
+fn indexed_sequences_preserve_errors() {
+    let mut buf = serde_mincode::serialize(&Indexed(vec![1_u32, 2])).unwrap();
+    buf[4..8].copy_from_slice(&100_u32.to_ne_bytes());
+
+    assert!(matches!(
+        *serde_mincode::deserialize::<Indexed<Vec<u32>>>(&buf).unwrap_err(),
+        Error::InvalidOffset
+    ));
+
+    let buf = serde_mincode::serialize(&Indexed(vec![1_u32])).unwrap();
+    let err = serde_mincode::deserialize::<Indexed<Vec<u64>>>(&buf).unwrap_err();
+    assert_eq!(err.needed(), Some(4));
+}
+
+#[test]
+fn indexed_maps_are_searched() {
+    let map: BTreeMap<String, Item> = items()
+        .into_iter()
+        .map(|item| (format!("key{}", item.id), item))
+        .collect();
+
+    for config in configs() {
+        let buf = config.serialize(&IndexedMap(map.clone())).unwrap();
+
+        let view = config
+            .deserialize::<IndexedMapView<String, Item>>(&buf)
+            .unwrap();
+        assert_eq!(view.len(), map.len());
+
+        for (key, item) in &map {
+            assert_eq!(view.get(key.as_str()).unwrap().as_ref(), Some(item));
+        }
+        assert_eq!(view.get("missing").unwrap(), None);
+
+        let IndexedMap(decoded) = config.deserialize(&buf).unwrap();
+        assert_eq!(decoded, map);
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Blob {
+    #[serde(with = "serde_mincode::attachment")]
+    data: Vec<u8>,
+}
+
+#[test]
+fn indexed_sequences_use_attachments() {
+    let value = Indexed(vec![Blob {
+        data: (1..=8).collect(),
+    }]);
+
+    let (buf, attachments) = serde_mincode::serialize_with_attachments(&value).unwrap();
+    let attachments = attachments.iter().map(Vec::as_slice).collect::<Vec<_>>();
+
+    let decoded =
+        serde_mincode::deserialize_with_attachments::<Indexed<Vec<Blob>>>(&buf, &attachments)
+            .unwrap();
+    assert_eq!(decoded, value);
+}
+
+#[test]
+fn indexed_views_do_not_support_attachments() {
+    let value = Indexed(vec![Blob {
+        data: (1..=8).collect(),
+    }]);
+
+    let (buf, attachments) = serde_mincode::serialize_with_attachments(&value).unwrap();
+    let attachments = attachments.iter().map(Vec::as_slice).collect::<Vec<_>>();
+
+    let res = serde_mincode::deserialize_with_attachments::<IndexedView<Blob>>(&buf, &attachments);
+    assert!(matches!(
+        res.map(|_| ()).map_err(|err| *err),
+        Err(Error::NotSupported)
+    ));
+}
+
+#[test]
+fn indexed_sequences_check_reported_lengths() {
+    struct Lying(usize, usize);
+
+    impl Serialize for Lying {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            struct Elements(usize, usize);
+
+            impl Serialize for Elements {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: serde::Serializer,
+                {
+                    let mut seq = serializer.serialize_seq(Some(self.0))?;
+                    for element in 0..self.1 {
+                        seq.serialize_element(&(element as u32))?;
+                    }
+                    seq.end()
+                }
+            }
+
+            serializer
+                .serialize_newtype_struct("$serde_mincode::Indexed", &Elements(self.0, self.1))
+        }
+    }
+
+    for (reported, actual) in [(2, 1), (1, 2)] {
+        assert!(matches!(
+            *serde_mincode::serialize(&Lying(reported, actual)).unwrap_err(),
+            Error::InvalidLength
+        ));
+    }
+    serde_mincode::serialize(&Lying(2, 2)).unwrap();
+}
@@ -0,0 +1,88 @@
+use std::io::Read;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::Deserialize;
+
+fn concatenated(values: &[(u8, String)]) -> Vec<u8> {
+    let mut buf = Vec::new();
+
+    for value in values {
+        serde_mincode::serialize_into(&mut buf, value).unwrap();
+    }
+
+    buf
+}
+
+fn values() -> Vec<(u8, String)> {
+    (0..5)
+        .map(|index| (index, "x".repeat(index as usize)))
+        .collect()
+}
+
+#[test]
+fn iterate_concatenated_values() {
+    let values = values();
+    let buf = concatenated(&values);
+
+    let decoded = serde_mincode::deserialize_iter::<(u8, String)>(&buf)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(decoded, values);
+
+    let decoded = serde_mincode::deserialize_iter_from_reader::<_, (u8, String)>(&buf[..])
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(decoded, values);
+}
+
+#[test]
+fn iterate_stops_after_truncated_final_value() {
+    let buf = concatenated(&values());
+    let buf = &buf[..buf.len() - 1];
+
+    let mut iter = serde_mincode::deserialize_iter::<(u8, String)>(buf);
+    assert_eq!(iter.by_ref().take(4).filter(Result::is_ok).count(), 4);
+    assert!(iter.next().unwrap().is_err());
+    assert!(iter.next().is_none());
+
+    let mut iter = serde_mincode::deserialize_iter_from_reader::<_, (u8, String)>(buf);
+    assert_eq!(iter.by_ref().take(4).filter(Result::is_ok).count(), 4);
+    assert!(iter.next().unwrap().is_err());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn reader_iterator_decodes_large_values_a_logarithmic_number_of_times() {
+    static ATTEMPTS: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counted(Vec<u64>);
+
+    impl<'de> Deserialize<'de> for Counted {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            ATTEMPTS.fetch_add(1, Ordering::Relaxed);
+
+            Vec::deserialize(deserializer).map(Self)
+        }
+    }
+
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let len = buf.len().min(1000);
+            self.0.read(&mut buf[..len])
+        }
+    }
+
+    let values = (0..512 * 1024).collect::<Vec<u64>>();
+    let buf = serde_mincode::serialize(&values).unwrap();
+
+    let mut iter = serde_mincode::deserialize_iter_from_reader::<_, Counted>(Trickle(&buf));
+    assert_eq!(iter.next().unwrap().unwrap().0, values);
+    assert!(iter.next().is_none());
+
+    assert!(ATTEMPTS.load(Ordering::Relaxed) <= 2 * buf.len().ilog2() as usize);
+}
@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use serde_mincode::{Config, Lazy, RawMincode};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Item {
+    id: u32,
+    name: Option<String>,
+}
+
+fn items() -> Vec<Item> {
+    (0..10)
+        .map(|id| Item {
+            id,
+            name: (id % 2 == 0).then(|| id.to_string()),
+        })
+        .collect()
+}
+
+fn configs() -> [Config; 3] {
+    [
+        Config::default(),
+        Config::default().presence_bitmap(true),
+        Config::default().canonical(true).field_lengths(true),
+    ]
+}
+
+#[derive(Serialize)]
+struct Envelope<'a> {
+    head: u32,
+    items: Lazy<'a, Vec<Item>>,
+    tail: u32,
+}
+
+#[derive(Deserialize)]
+struct LazyEnvelope<'a, T = Vec<Item>> {
+    head: u32,
+    #[serde(borrow)]
+    items: Lazy<'a, T>,
+    tail: u32,
+}
+
+fn envelope() -> Envelope<'static> {
+    Envelope {
+        head: 1,
+        items: Lazy::new(items()),
+        tail: 2,
+    }
+}
+
+#[test]
+fn lazy_values_use_the_outer_config() {
+    for config in configs() {
+        let buf = config.serialize(&envelope()).unwrap();
+
+        let envelope = config.deserialize::<LazyEnvelope>(&buf).unwrap();
+        assert_eq!((envelope.head, envelope.tail), (1, 2));
+        assert_eq!(envelope.items.get().unwrap(), items());
+
+        let raw = envelope.items.encoded().unwrap();
+        assert_eq!(
+            raw.as_bytes(),
+            RawMincode::from_value_with_config(&items(), config)
+                .unwrap()
+                .as_bytes()
+        );
+
+        let forwarded = Envelope {
+            head: 1,
+            items: Lazy::from_encoded(raw.clone()),
+            tail: 2,
+        };
+        assert_eq!(config.serialize(&forwarded).unwrap(), buf);
+    }
+}
+
+/// Does not implement `Clone`
+#[derive(Debug, PartialEq, Deserialize)]
+struct Borrowed<'a> {
+    id: u32,
+    name: Option<&'a str>,
+}
+
+#[test]
+fn lazy_values_are_decoded_without_cloning() {
+    for config in configs() {
+        let buf = config.serialize(&envelope()).unwrap();
+
+        let envelope = config
+            .deserialize::<LazyEnvelope<'_, Vec<Borrowed>>>(&buf)
+            .unwrap();
+        let decoded = envelope.items.decode().unwrap().unwrap();
+        assert_eq!(decoded.len(), 10);
+        assert_eq!(decoded[2].name, Some("2"));
+    }
+
+    assert!(Lazy::new(items()).decode().is_none());
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "mmap")]
+
+use std::env::temp_dir;
+use std::fs::{remove_file, write};
+use std::process::id;
+
+use serde_mincode::{Config, MappedFile};
+
+#[test]
+fn mapped_files_are_deserialized() {
+    let config = Config::default().presence_bitmap(true);
+    let path = temp_dir().join(format!("serde-mincode-mmap-{}", id()));
+
+    let mut buf = Vec::new();
+    for index in 0..3_u8 {
+        config
+            .serialize_into(&mut buf, &(index, Some("x".repeat(index as usize))))
+            .unwrap();
+    }
+    write(&path, &buf).unwrap();
+
+    let file = unsafe { MappedFile::open_with_config(&path, config).unwrap() };
+    assert_eq!(file.as_bytes(), buf);
+
+    let (index, value) = file.deserialize::<(u8, Option<&str>)>().unwrap();
+    assert_eq!((index, value), (0, Some("")));
+
+    let values = file
+        .deserialize_iter::<(u8, Option<&str>)>()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(values, [(0, Some("")), (1, Some("x")), (2, Some("xx"))]);
+
+    drop(file);
+    remove_file(&path).unwrap();
+}
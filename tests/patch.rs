@@ -0,0 +1,106 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_mincode::{Config, Error};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Record {
+    id: u64,
+    name: String,
+    note: Option<String>,
+    score: Option<f64>,
+    tags: Vec<String>,
+    attrs: BTreeMap<String, i32>,
+    kind: Kind,
+    inner: Inner,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+struct Inner {
+    flag: bool,
+    values: Vec<u16>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Kind {
+    Unit,
+    Newtype(u8),
+    Struct { x: Option<i8>, y: char },
+}
+
+fn record() -> Record {
+    Record {
+        id: 42,
+        name: "answer".to_owned(),
+        note: None,
+        score: Some(0.5),
+        tags: vec!["a".to_owned(), "b".to_owned()],
+        attrs: [("x".to_owned(), 1), ("y".to_owned(), -1)].into(),
+        kind: Kind::Struct { x: None, y: 'z' },
+        inner: Inner {
+            flag: true,
+            values: Vec::new(),
+        },
+    }
+}
+
+fn configs() -> Vec<Config> {
+    let mut configs = Vec::new();
+
+    for bits in 0..8 {
+        configs.push(
+            Config::default()
+                .presence_bitmap(bits & 1 != 0)
+                .field_lengths(bits & 2 != 0)
+                .canonical(bits & 4 != 0),
+        );
+    }
+
+    configs
+}
+
+#[test]
+fn patch_fixed_width_fields() {
+    for config in configs() {
+        let mut value = record();
+        let mut buf = config.serialize(&value).unwrap();
+
+        config.patch::<Record, _>(&mut buf, "id", &7_u64).unwrap();
+        config
+            .patch::<Record, _>(&mut buf, "score", &Some(1.5_f64))
+            .unwrap();
+        value.id = 7;
+        value.score = Some(1.5);
+        assert_eq!(config.deserialize::<Record>(&buf).unwrap(), value);
+
+        let prev = buf.clone();
+        assert!(config
+            .patch::<Record, _>(&mut buf, "name", &u64::MAX)
+            .is_err());
+        assert!(config
+            .patch::<Record, _>(&mut buf, "name", &u16::MAX)
+            .is_err());
+        assert!(matches!(
+            *config
+                .patch::<Record, _>(&mut buf, "missing", &0_u64)
+                .unwrap_err(),
+            Error::UnknownPath
+        ));
+        assert_eq!(buf, prev);
+    }
+}
+
+#[test]
+fn locate_nested_fields() {
+    for config in configs() {
+        let mut value = record();
+        let mut buf = config.serialize(&value).unwrap();
+
+        let range = config.locate::<Record>(&buf, "inner.flag").unwrap();
+        assert_eq!(range.len(), 1);
+
+        config.patch_at(&mut buf, range, &false).unwrap();
+        value.inner.flag = false;
+        assert_eq!(config.deserialize::<Record>(&buf).unwrap(), value);
+    }
+}
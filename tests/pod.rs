@@ -0,0 +1,23 @@
+#![cfg(feature = "bytemuck")]
+
+use serde_mincode::{Config, Pod};
+
+#[test]
+fn pod_is_encoded_like_vec() {
+    let values = vec![1.5_f32, -2.0, f32::INFINITY];
+
+    let buf = serde_mincode::serialize(&Pod(values.clone())).unwrap();
+    assert_eq!(buf, serde_mincode::serialize(&values).unwrap());
+
+    let Pod(decoded) = serde_mincode::deserialize::<Pod<Vec<f32>>>(&buf).unwrap();
+    assert_eq!(decoded, values);
+    let Pod(decoded) = serde_mincode::deserialize::<Pod<Box<[f32]>>>(&buf).unwrap();
+    assert_eq!(*decoded, values);
+    let decoded = serde_mincode::deserialize::<Vec<f32>>(&buf).unwrap();
+    assert_eq!(decoded, values);
+
+    let config = Config::default().aligned(true);
+    let buf = config.serialize(&(1_u8, Pod(values.clone()))).unwrap();
+    let (_, Pod(decoded)) = config.deserialize::<(u8, Pod<Vec<f32>>)>(&buf).unwrap();
+    assert_eq!(decoded, values);
+}
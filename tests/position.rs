@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use serde_mincode::{Config, Decoder, Encoder};
+
+#[test]
+fn decoder_tracks_position_and_remaining_input() {
+    let mut buf = serde_mincode::serialize(&1_u32).unwrap();
+    serde_mincode::serialize_into(&mut buf, &"two").unwrap();
+
+    let mut decoder = Decoder::new(&buf);
+    assert_eq!(decoder.position(), 0);
+    assert_eq!(decoder.remaining(), &buf[..]);
+
+    assert_eq!(u32::deserialize(&mut decoder).unwrap(), 1);
+    assert_eq!(decoder.position(), 4);
+    assert_eq!(decoder.remaining(), &buf[4..]);
+    assert!(!decoder.is_empty());
+
+    assert_eq!(<&str>::deserialize(&mut decoder).unwrap(), "two");
+    assert_eq!(decoder.position(), buf.len());
+    assert!(decoder.is_empty());
+}
+
+#[test]
+fn encoder_counts_bytes_written() {
+    let mut buf = vec![0xff; 3];
+
+    let encoder = (1_u8, "two")
+        .serialize(Encoder::with_config(&mut buf, Config::default()))
+        .unwrap();
+    assert_eq!(encoder.bytes_written(), 8);
+
+    assert_eq!(&buf[..3], [0xff; 3]);
+    assert_eq!(
+        serde_mincode::deserialize::<(u8, &str)>(&buf[3..]).unwrap(),
+        (1, "two")
+    );
+}
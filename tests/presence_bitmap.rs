@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use serde_mincode::Config;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Record {
+    id: u64,
+    note: Option<String>,
+    score: Option<f64>,
+    tags: Vec<String>,
+    kind: Kind,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Sparse {
+    flag: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    values: Vec<u16>,
+    note: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Skipped {
+    flag: bool,
+    #[serde(default, skip_serializing_if = "always")]
+    cache: u32,
+    note: Option<String>,
+}
+
+fn always<T>(_: &T) -> bool {
+    true
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Kind {
+    Unit,
+    Struct { x: Option<i8>, y: char },
+}
+
+#[test]
+fn round_trip_with_and_without_presence_bitmap() {
+    let mut value = Record {
+        id: 42,
+        note: None,
+        score: Some(0.5),
+        tags: vec!["a".to_owned(), "b".to_owned()],
+        kind: Kind::Unit,
+    };
+
+    for enabled in [false, true] {
+        let config = Config::default().presence_bitmap(enabled);
+
+        for kind in [Kind::Unit, Kind::Struct { x: None, y: 'z' }] {
+            value.kind = kind;
+            value.note = value.note.take().xor(Some("note".to_owned()));
+
+            let buf = config.serialize(&value).unwrap();
+            assert_eq!(config.deserialize::<Record>(&buf).unwrap(), value);
+        }
+    }
+}
+
+#[test]
+fn presence_bitmap_omits_none_and_skipped_fields() {
+    let mut value = Sparse {
+        flag: true,
+        values: Vec::new(),
+        note: None,
+    };
+
+    let plain = serde_mincode::serialize(&value).unwrap();
+    assert!(serde_mincode::deserialize::<Sparse>(&plain).is_err());
+
+    let config = Config::default().presence_bitmap(true);
+    let buf = config.serialize(&value).unwrap();
+    assert_eq!(buf, [0b001, 1]);
+    assert_eq!(config.deserialize::<Sparse>(&buf).unwrap(), value);
+
+    value.values.push(1);
+    value.note = Some("note".to_owned());
+    let buf = config.serialize(&value).unwrap();
+    assert_eq!(buf[0], 0b111);
+    assert_eq!(config.deserialize::<Sparse>(&buf).unwrap(), value);
+}
+
+#[test]
+fn presence_bitmap_supports_always_skipped_fields() {
+    let config = Config::default().presence_bitmap(true);
+
+    let value = Skipped {
+        flag: true,
+        cache: 7,
+        note: Some("note".to_owned()),
+    };
+    let buf = config.serialize(&value).unwrap();
+    assert_eq!(buf[0], 0b101);
+    assert_eq!(
+        config.deserialize::<Skipped>(&buf).unwrap(),
+        Skipped { cache: 0, ..value }
+    );
+}
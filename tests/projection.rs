@@ -0,0 +1,110 @@
+use std::collections::BTreeMap;
+
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Serialize};
+use serde_mincode::{Config, Error};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Record {
+    id: u64,
+    name: String,
+    note: Option<String>,
+    score: Option<f64>,
+    tags: Vec<String>,
+    attrs: BTreeMap<String, i32>,
+    kind: Kind,
+    inner: Inner,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+struct Inner {
+    flag: bool,
+    values: Vec<u16>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Kind {
+    Unit,
+    Newtype(u8),
+    Struct { x: Option<i8>, y: char },
+}
+
+fn record() -> Record {
+    Record {
+        id: 42,
+        name: "answer".to_owned(),
+        note: None,
+        score: Some(0.5),
+        tags: vec!["a".to_owned(), "b".to_owned()],
+        attrs: [("x".to_owned(), 1), ("y".to_owned(), -1)].into(),
+        kind: Kind::Struct { x: None, y: 'z' },
+        inner: Inner {
+            flag: true,
+            values: Vec::new(),
+        },
+    }
+}
+
+#[test]
+fn round_trip_with_field_lengths() {
+    let mut value = record();
+
+    for presence_bitmap in [false, true] {
+        let config = Config::default()
+            .field_lengths(true)
+            .presence_bitmap(presence_bitmap);
+
+        for kind in [Kind::Unit, Kind::Newtype(7), value.kind.clone()] {
+            value.kind = kind;
+            value.note = value.note.take().xor(Some("note".to_owned()));
+
+            let buf = config.serialize(&value).unwrap();
+            assert_eq!(config.deserialize::<Record>(&buf).unwrap(), value);
+        }
+    }
+}
+
+#[test]
+fn projection_skips_unselected_fields() {
+    #[derive(Debug, Default, Deserialize)]
+    #[serde(default)]
+    struct Projected {
+        id: u64,
+        name: IgnoredAny,
+        note: IgnoredAny,
+        score: IgnoredAny,
+        tags: IgnoredAny,
+        attrs: IgnoredAny,
+        kind: IgnoredAny,
+        inner: ProjectedInner,
+    }
+
+    #[derive(Debug, Default, Deserialize)]
+    #[serde(default)]
+    struct ProjectedInner {
+        flag: bool,
+        values: IgnoredAny,
+    }
+
+    let config = Config::default().field_lengths(true);
+    let buf = config.serialize(&record()).unwrap();
+
+    let projected = config
+        .deserialize_projected::<Projected>(&buf, &["inner.flag"])
+        .unwrap();
+    assert_eq!(projected.id, 0);
+    assert!(projected.inner.flag);
+
+    let projected = config
+        .deserialize_projected::<Projected>(&buf, &["id"])
+        .unwrap();
+    assert_eq!(projected.id, 42);
+    assert!(!projected.inner.flag);
+
+    assert!(matches!(
+        *Config::default()
+            .deserialize_projected::<Projected>(&buf, &["id"])
+            .unwrap_err(),
+        Error::NotSupported
+    ));
+}
@@ -0,0 +1,40 @@
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+use serde_mincode::RawMincode;
+
+#[derive(Serialize, Deserialize)]
+struct Envelope<'a> {
+    kind: u8,
+    #[serde(borrow)]
+    payload: RawMincode<'a>,
+}
+
+#[test]
+fn raw_values_are_embedded_verbatim() {
+    let payload = RawMincode::from_value(&(1_u32, "payload")).unwrap();
+    assert_eq!(
+        payload.as_bytes(),
+        serde_mincode::serialize(&(1_u32, "payload")).unwrap()
+    );
+
+    let buf = serde_mincode::serialize(&Envelope {
+        kind: 7,
+        payload: payload.clone(),
+    })
+    .unwrap();
+
+    let envelope = serde_mincode::deserialize::<Envelope>(&buf).unwrap();
+    assert_eq!(envelope.kind, 7);
+    assert_eq!(envelope.payload, payload);
+    assert!(buf
+        .as_ptr_range()
+        .contains(&envelope.payload.as_bytes().as_ptr()));
+
+    let (id, name) = envelope.payload.deserialize::<(u32, Cow<str>)>().unwrap();
+    assert_eq!((id, &*name), (1, "payload"));
+
+    let owned = envelope.payload.into_owned();
+    drop(buf);
+    assert_eq!(owned, payload);
+}
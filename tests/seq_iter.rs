@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use serde_mincode::{Config, Lazy, SeqIter};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Item {
+    id: u32,
+    name: Option<String>,
+}
+
+fn items() -> Vec<Item> {
+    (0..10)
+        .map(|id| Item {
+            id,
+            name: (id % 2 == 0).then(|| id.to_string()),
+        })
+        .collect()
+}
+
+fn configs() -> [Config; 3] {
+    [
+        Config::default(),
+        Config::default().presence_bitmap(true),
+        Config::default().canonical(true).field_lengths(true),
+    ]
+}
+
+#[derive(Serialize)]
+struct Envelope<'a> {
+    head: u32,
+    items: Lazy<'a, Vec<Item>>,
+    tail: u32,
+}
+
+#[derive(Deserialize)]
+struct StreamedEnvelope<'a> {
+    head: u32,
+    #[serde(borrow)]
+    items: Lazy<'a, SeqIter<'a, Item>>,
+    tail: u32,
+}
+
+#[derive(Serialize)]
+struct Batch {
+    head: u32,
+    items: Vec<Item>,
+}
+
+#[derive(Deserialize)]
+struct StreamedBatch<'a> {
+    head: u32,
+    #[serde(borrow)]
+    items: SeqIter<'a, Item>,
+}
+
+#[test]
+fn sequence_iterators_decode_vectors() {
+    for config in configs() {
+        let buf = config.serialize(&items()).unwrap();
+
+        let iter = config.deserialize::<SeqIter<Item>>(&buf).unwrap();
+        assert_eq!(iter.len(), 10);
+        assert_eq!(iter.collect::<Result<Vec<_>, _>>().unwrap(), items());
+
+        let buf = config
+            .serialize(&Batch {
+                head: 1,
+                items: items(),
+            })
+            .unwrap();
+
+        let batch = config.deserialize::<StreamedBatch>(&buf).unwrap();
+        assert_eq!(batch.head, 1);
+        assert_eq!(batch.items.collect::<Result<Vec<_>, _>>().unwrap(), items());
+    }
+}
+
+#[test]
+fn sequence_iterators_can_be_followed_by_other_values_when_lazy() {
+    for config in configs() {
+        let buf = config
+            .serialize(&Envelope {
+                head: 1,
+                items: Lazy::new(items()),
+                tail: 2,
+            })
+            .unwrap();
+
+        let envelope = config.deserialize::<StreamedEnvelope>(&buf).unwrap();
+        assert_eq!((envelope.head, envelope.tail), (1, 2));
+
+        let iter = envelope.items.decode().unwrap().unwrap();
+        assert_eq!(iter.len(), 10);
+        assert_eq!(iter.collect::<Result<Vec<_>, _>>().unwrap(), items());
+    }
+}
+
+#[test]
+fn sequence_iterators_detect_unconsumed_elements() {
+    let mut buf = serde_mincode::serialize(&vec![1_u32, 2]).unwrap();
+    buf.push(3);
+
+    let mut iter = serde_mincode::deserialize::<SeqIter<u32>>(&buf).unwrap();
+    assert_eq!(iter.next().unwrap().unwrap(), 1);
+    assert!(matches!(
+        *iter.next().unwrap().unwrap_err(),
+        serde_mincode::Error::UnconsumedElements
+    ));
+    assert!(iter.next().is_none());
+}
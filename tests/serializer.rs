@@ -0,0 +1,56 @@
+use serde::ser::{Error as _, Serialize};
+use serde_mincode::{Config, Serializer};
+
+struct Failing;
+
+impl Serialize for Failing {
+    fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Err(S::Error::custom("failing"))
+    }
+}
+
+#[test]
+fn serializer_reuses_its_buffer() {
+    let mut serializer = Serializer::new();
+
+    let ptr = serializer.serialize(&"x".repeat(100)).unwrap().as_ptr();
+    assert_eq!(
+        serializer.serialize(&(1_u8, 2_u16)).unwrap(),
+        serde_mincode::serialize(&(1_u8, 2_u16)).unwrap()
+    );
+    assert_eq!(serializer.as_bytes().as_ptr(), ptr);
+
+    assert!(serializer.serialize(&(1_u8, Failing)).is_err());
+    assert!(serializer.as_bytes().is_empty());
+
+    serializer.serialize(&1_u8).unwrap();
+    serializer.clear();
+    assert!(serializer.as_bytes().is_empty());
+}
+
+#[test]
+fn serializer_uses_its_config() {
+    let config = Config::default().presence_bitmap(true);
+    let mut serializer = Serializer::with_config(config);
+
+    let value = Some(1_u8);
+    assert_eq!(
+        serializer.serialize(&value).unwrap(),
+        config.serialize(&value).unwrap()
+    );
+}
+
+#[test]
+fn thread_local_serializer_can_be_nested() {
+    let (outer, inner) = Serializer::with_thread_local(|outer| {
+        let inner = Serializer::with_thread_local(|inner| inner.serialize(&2_u8).unwrap().to_vec());
+
+        (outer.serialize(&1_u8).unwrap().to_vec(), inner)
+    });
+
+    assert_eq!(outer, [1]);
+    assert_eq!(inner, [2]);
+}
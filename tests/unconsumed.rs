@@ -0,0 +1,40 @@
+use serde::Deserialize;
+use serde_mincode::Error;
+
+#[test]
+fn unconsumed_elements_are_rejected() {
+    #[derive(Debug)]
+    struct First(#[allow(dead_code)] u32);
+
+    impl<'de> Deserialize<'de> for First {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            struct Visitor;
+
+            impl<'de> serde::de::Visitor<'de> for Visitor {
+                type Value = First;
+
+                fn expecting(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    fmt.write_str("sequence")
+                }
+
+                fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+                where
+                    A: serde::de::SeqAccess<'de>,
+                {
+                    Ok(First(seq.next_element()?.unwrap()))
+                }
+            }
+
+            deserializer.deserialize_seq(Visitor)
+        }
+    }
+
+    let buf = serde_mincode::serialize(&vec![1_u32, 2]).unwrap();
+    assert!(matches!(
+        *serde_mincode::deserialize::<First>(&buf).unwrap_err(),
+        Error::UnconsumedElements
+    ));
+}
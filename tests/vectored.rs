@@ -0,0 +1,47 @@
+#![cfg(feature = "bytes")]
+
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use serde_mincode::Config;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Message {
+    id: u32,
+    #[serde(with = "serde_mincode::bytes")]
+    small: Bytes,
+    #[serde(with = "serde_mincode::bytes")]
+    large: Bytes,
+    note: Option<String>,
+}
+
+fn message() -> Message {
+    Message {
+        id: 1,
+        small: Bytes::from_static(b"small"),
+        large: Bytes::from(vec![0xab; 1024]),
+        note: Some("note".to_owned()),
+    }
+}
+
+#[test]
+fn vectored_output_references_large_bytes() {
+    for config in [Config::default(), Config::default().presence_bitmap(true)] {
+        let value = message();
+        let vectored = config.serialize_vectored(&value, 256).unwrap();
+
+        let slices = vectored.io_slices();
+        assert!(slices
+            .iter()
+            .any(|slice| slice.as_ptr() == value.large.as_ptr()));
+        assert!(!slices
+            .iter()
+            .any(|slice| slice.as_ptr() == value.small.as_ptr()));
+
+        let buf = slices
+            .iter()
+            .flat_map(|slice| slice.iter().copied())
+            .collect::<Vec<_>>();
+        assert_eq!(buf, config.serialize(&value).unwrap());
+        assert_eq!(config.deserialize::<Message>(&buf).unwrap(), value);
+    }
+}